@@ -0,0 +1,80 @@
+// Throughput of the day 1 part 1 digit scan over a generated document.
+//
+//     cargo bench --bench digit_scan
+//
+// DIGIT_SCAN_MB sets the document size (default 256).
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use adventofcode_2023::{
+    calibration_digit_sum, first_calibration_digit_scalar, last_calibration_digit_scalar,
+    Automaton, CalibrationGenerator,
+};
+
+const RUNS: usize = 5;
+
+fn document(megabytes: usize) -> (Vec<u8>, u64) {
+    let chunk = CalibrationGenerator::new(2023)
+        .with_lines(100_000)
+        .generate();
+    let text = chunk.text();
+    let repeats = (megabytes << 20).div_ceil(text.len());
+
+    (
+        text.as_bytes().repeat(repeats),
+        chunk.part_1 * repeats as u64,
+    )
+}
+
+fn line_sum(
+    input: &[u8],
+    first: impl Fn(&[u8]) -> Option<usize>,
+    last: impl Fn(&[u8]) -> Option<usize>,
+) -> u64 {
+    input
+        .split(|&b| b == b'\n')
+        .filter_map(|line| {
+            Some(((line[first(line)?] - b'0') * 10 + (line[last(line)?] - b'0')) as u64)
+        })
+        .sum()
+}
+
+fn measure(name: &str, input: &[u8], expected: u64, scan: impl Fn(&[u8]) -> u64) {
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let sum = scan(input);
+        best = best.min(start.elapsed());
+        assert_eq!(expected, sum, "{} got the wrong answer", name);
+    }
+
+    let throughput = input.len() as f64 / best.as_secs_f64() / (1 << 20) as f64;
+    println!("{:>10}: {:>10.3?} {:>10.1} MiB/s", name, best, throughput);
+}
+
+fn main() {
+    let megabytes = env::var("DIGIT_SCAN_MB")
+        .ok()
+        .and_then(|mb| mb.parse().ok())
+        .unwrap_or(256);
+    let (input, expected) = document(megabytes);
+    println!("{} MiB, part 1 answer {}", input.len() >> 20, expected);
+
+    let automaton = Automaton::new(["1", "2", "3", "4", "5", "6", "7", "8", "9"]);
+    measure("automaton", &input, expected, |input| {
+        line_sum(
+            input,
+            |line| automaton.find_first(line).map(|m| m.start),
+            |line| automaton.find_last(line).map(|m| m.start),
+        )
+    });
+    measure("scalar", &input, expected, |input| {
+        line_sum(
+            input,
+            first_calibration_digit_scalar,
+            last_calibration_digit_scalar,
+        )
+    });
+    measure("simd", &input, expected, calibration_digit_sum);
+}
@@ -0,0 +1,86 @@
+// Day 2 parsing throughput over a simulated document: building full `Game`
+// values against the allocation-free scan, and the solvers built on the scan.
+//
+//     cargo bench --bench game_parser
+//
+// GAME_PARSER_LINES sets the number of games (default 1000000).
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use adventofcode_2023::{
+    scan_game, solve_day_2_part_1, solve_day_2_part_2, Answer, Bag, Game, GameSimulator,
+};
+
+const RUNS: usize = 5;
+
+fn measure(
+    name: &str,
+    lines: &[&str],
+    expected: (u64, u64),
+    parse: impl Fn(&str) -> (u64, u64),
+) -> Duration {
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let answers = lines.iter().fold((0, 0), |(ids, power), line| {
+            let (id, p) = parse(line);
+            (ids + id, power + p)
+        });
+        best = best.min(start.elapsed());
+        assert_eq!(expected, answers, "{} got the wrong answer", name);
+    }
+
+    let throughput = lines.len() as f64 / best.as_secs_f64() / 1e6;
+    println!("{:>6}: {:>10.3?} {:>8.2} M lines/s", name, best, throughput);
+    best
+}
+
+fn main() {
+    let games = env::var("GAME_PARSER_LINES")
+        .ok()
+        .and_then(|lines| lines.parse().ok())
+        .unwrap_or(1_000_000);
+    let document = GameSimulator::new(2023).with_games(games).generate();
+    let text = document.text();
+    let lines: Vec<&str> = text.lines().collect();
+    let expected = (document.part_1, document.part_2);
+    println!(
+        "{} games, {} MiB, answers {:?}",
+        lines.len(),
+        text.len() >> 20,
+        expected
+    );
+
+    let bag: Bag = "12 red, 13 green, 14 blue".parse().unwrap();
+    let parsed = measure("parse", &lines, expected, |line| {
+        let game: Game = line.parse().unwrap();
+        let id = match game.is_possible_with(&bag) {
+            true => game.id,
+            false => 0,
+        };
+        (id as u64, game.power())
+    });
+    let scanned = measure("scan", &lines, expected, |line| {
+        let tally = scan_game(line).unwrap();
+        let id = match tally.is_possible_with(&bag) {
+            true => tally.id,
+            false => 0,
+        };
+        (id as u64, tally.power())
+    });
+
+    println!(
+        "speedup: {:.1}x",
+        parsed.as_secs_f64() / scanned.as_secs_f64()
+    );
+
+    let start = Instant::now();
+    let answers = (solve_day_2_part_1(&lines), solve_day_2_part_2(&lines));
+    println!("{:>6}: {:>10.3?}", "solve", start.elapsed());
+    assert_eq!(
+        (Answer::from(document.part_1), Answer::from(document.part_2)),
+        answers,
+        "the day 2 solvers got the wrong answer"
+    );
+}
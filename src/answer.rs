@@ -0,0 +1,155 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(u128),
+    Text(String),
+}
+
+impl Answer {
+    pub fn matches(&self, expected: &str) -> bool {
+        let text = self.to_string();
+        text == expected.trim()
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_string() == other.to_string()
+    }
+}
+
+impl Eq for Answer {}
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        self.matches(other)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self.matches(other)
+    }
+}
+
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let answer = if let Ok(n) = s.parse::<u64>() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse::<i64>() {
+            Answer::Signed(n)
+        } else if let Ok(n) = s.parse::<u128>() {
+            Answer::Big(n)
+        } else {
+            Answer::Text(s.to_string())
+        };
+
+        Ok(answer)
+    }
+}
+
+macro_rules! answer_from {
+    ($($source:ty => $variant:ident,)*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+answer_from! {
+    i32 => Signed,
+    i64 => Signed,
+    u32 => Unsigned,
+    u64 => Unsigned,
+    u128 => Big,
+    String => Text,
+    &str => Text,
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        match u64::try_from(value) {
+            Ok(n) => Answer::Unsigned(n),
+            Err(_) => Answer::Big(value as u128),
+        }
+    }
+}
+
+#[cfg(test)]
+mod answer_tests {
+    use super::*;
+
+    macro_rules! display_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (input, expected): (Answer, &str) = $value;
+                    assert_eq!(expected, input.to_string())
+                }
+            )*
+        }
+    }
+
+    display_tests! {
+        display_signed: (Answer::Signed(-42), "-42"),
+        display_unsigned: (Answer::Unsigned(8549735), "8549735"),
+        display_big: (Answer::Big(u128::MAX), "340282366920938463463374607431768211455"),
+        display_text: (Answer::Text(String::from("ABCDEFG")), "ABCDEFG"),
+    }
+
+    #[test]
+    fn answers_with_same_value_are_equal_across_variants() {
+        assert_eq!(Answer::Signed(142), Answer::Unsigned(142));
+        assert_eq!(Answer::Unsigned(142), Answer::Big(142));
+        assert_ne!(Answer::Signed(-1), Answer::Unsigned(1))
+    }
+
+    #[test]
+    fn answer_matches_text_from_answers_file() {
+        let answer = Answer::from(56397);
+
+        assert!(answer.matches("56397\n"));
+        assert!(answer == "56397");
+        assert!(!answer.matches("56398"))
+    }
+
+    #[test]
+    fn from_str_picks_narrowest_variant() {
+        assert!(matches!("12".parse(), Ok(Answer::Unsigned(12))));
+        assert!(matches!("-12".parse(), Ok(Answer::Signed(-12))));
+        assert!(matches!(
+            "18446744073709551616".parse(),
+            Ok(Answer::Big(18446744073709551616))
+        ));
+        assert!(matches!("EHZ".parse(), Ok(Answer::Text(_))))
+    }
+
+    #[test]
+    fn from_usize_is_unsigned() {
+        assert!(matches!(Answer::from(30usize), Answer::Unsigned(30)))
+    }
+}
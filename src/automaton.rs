@@ -0,0 +1,348 @@
+use std::collections::VecDeque;
+
+const FAIL: u32 = u32::MAX;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+// A fully resolved Aho-Corasick DFA: every state has a transition for every
+// byte, so scanning is a single table lookup per byte with no failure chasing.
+struct Dfa {
+    next: Vec<[u32; 256]>,
+    outputs: Vec<Vec<usize>>,
+}
+
+impl Dfa {
+    fn new(patterns: &[Vec<u8>]) -> Self {
+        let mut next = vec![[FAIL; 256]];
+        let mut outputs = vec![vec![]];
+
+        for (id, bytes) in patterns.iter().enumerate() {
+            let mut state = 0;
+            for &b in bytes {
+                let target = next[state][b as usize];
+                state = if target == FAIL {
+                    next.push([FAIL; 256]);
+                    outputs.push(vec![]);
+                    let created = next.len() - 1;
+                    next[state][b as usize] = created as u32;
+                    created
+                } else {
+                    target as usize
+                };
+            }
+            outputs[state].push(id);
+        }
+
+        let mut fail = vec![0; next.len()];
+        let mut queue = VecDeque::new();
+        for target in next[0].iter_mut() {
+            match *target {
+                FAIL => *target = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let fallback = next[fail[state]];
+            let mut children = vec![];
+            for (target, via_fail) in next[state].iter_mut().zip(fallback) {
+                match *target {
+                    FAIL => *target = via_fail,
+                    child => children.push((child as usize, via_fail as usize)),
+                }
+            }
+
+            for (child, via_fail) in children {
+                fail[child] = via_fail;
+                let inherited = outputs[via_fail].clone();
+                outputs[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        Self { next, outputs }
+    }
+
+    fn step(&self, state: usize, byte: u8) -> usize {
+        self.next[state][byte as usize] as usize
+    }
+
+    // Makes upper case ASCII letters behave exactly like their lower case
+    // counterparts. Only valid when every pattern is lower case.
+    fn fold_ascii_case(&mut self) {
+        for row in self.next.iter_mut() {
+            for upper in b'A'..=b'Z' {
+                row[upper as usize] = row[upper.to_ascii_lowercase() as usize];
+            }
+        }
+    }
+}
+
+pub struct Automaton {
+    forward: Dfa,
+    reverse: Dfa,
+    lengths: Vec<usize>,
+    max_len: usize,
+}
+
+impl Automaton {
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        Self::build(patterns, false)
+    }
+
+    // Matches ASCII letters regardless of case. Non-ASCII bytes must match
+    // exactly.
+    pub fn ascii_case_insensitive<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        Self::build(patterns, true)
+    }
+
+    fn build<I, P>(patterns: I, fold_case: bool) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let patterns: Vec<Vec<u8>> = patterns
+            .into_iter()
+            .map(|p| match fold_case {
+                true => p.as_ref().to_ascii_lowercase(),
+                false => p.as_ref().to_vec(),
+            })
+            .collect();
+        assert!(
+            patterns.iter().all(|p| !p.is_empty()),
+            "automaton patterns must not be empty"
+        );
+
+        let reversed: Vec<Vec<u8>> = patterns
+            .iter()
+            .map(|p| p.iter().rev().copied().collect())
+            .collect();
+        let mut forward = Dfa::new(&patterns);
+        let mut reverse = Dfa::new(&reversed);
+        if fold_case {
+            forward.fold_ascii_case();
+            reverse.fold_ascii_case();
+        }
+        let lengths: Vec<usize> = patterns.iter().map(|p| p.len()).collect();
+        let max_len = lengths.iter().copied().max().unwrap_or(0);
+
+        Self {
+            forward,
+            reverse,
+            lengths,
+            max_len,
+        }
+    }
+
+    pub fn pattern_count(&self) -> usize {
+        self.lengths.len()
+    }
+
+    fn to_match(&self, pattern: usize, end: usize) -> Match {
+        Match {
+            pattern,
+            start: end - self.lengths[pattern],
+            end,
+        }
+    }
+
+    pub fn find_overlapping<'a>(&'a self, haystack: &'a [u8]) -> Matches<'a> {
+        Matches {
+            automaton: self,
+            haystack,
+            position: 0,
+            state: 0,
+            output: 0,
+        }
+    }
+
+    // The match with the leftmost start, preferring the longest pattern among
+    // those sharing it. Matches are discovered in order of their end, so
+    // scanning continues only while a longer pattern could still start
+    // earlier.
+    pub fn find_first(&self, haystack: &[u8]) -> Option<Match> {
+        let mut best: Option<Match> = None;
+        let mut state = 0;
+
+        for (i, &b) in haystack.iter().enumerate() {
+            if best.is_some_and(|m| i >= m.start + self.max_len) {
+                break;
+            }
+
+            state = self.forward.step(state, b);
+            for &pattern in self.forward.outputs[state].iter() {
+                let found = self.to_match(pattern, i + 1);
+                if best.is_none_or(|m| (found.start, m.end) < (m.start, found.end)) {
+                    best = Some(found);
+                }
+            }
+        }
+
+        best
+    }
+
+    // The match with the rightmost start. Scanning the reversed haystack with
+    // reversed patterns reports matches in order of decreasing start, so the
+    // first one found is the answer; a state lists its longest output first.
+    pub fn find_last(&self, haystack: &[u8]) -> Option<Match> {
+        let mut state = 0;
+
+        for (i, &b) in haystack.iter().enumerate().rev() {
+            state = self.reverse.step(state, b);
+            if let Some(&pattern) = self.reverse.outputs[state].first() {
+                return Some(Match {
+                    pattern,
+                    start: i,
+                    end: i + self.lengths[pattern],
+                });
+            }
+        }
+
+        None
+    }
+}
+
+pub struct Matches<'a> {
+    automaton: &'a Automaton,
+    haystack: &'a [u8],
+    position: usize,
+    state: usize,
+    output: usize,
+}
+
+impl Iterator for Matches<'_> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        let dfa = &self.automaton.forward;
+
+        loop {
+            if let Some(&pattern) = dfa.outputs[self.state].get(self.output) {
+                self.output += 1;
+                return Some(self.automaton.to_match(pattern, self.position));
+            }
+
+            let &b = self.haystack.get(self.position)?;
+            self.state = dfa.step(self.state, b);
+            self.position += 1;
+            self.output = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod automaton_tests {
+    use super::*;
+
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    fn spans(automaton: &Automaton, haystack: &str) -> Vec<(usize, usize, usize)> {
+        automaton
+            .find_overlapping(haystack.as_bytes())
+            .map(|m| (m.pattern, m.start, m.end))
+            .collect()
+    }
+
+    #[test]
+    fn find_overlapping_reports_shared_letters() {
+        let automaton = Automaton::new(WORDS);
+
+        assert_eq!(vec![(7, 0, 5), (1, 4, 7)], spans(&automaton, "eightwo"));
+        assert_eq!(vec![(0, 1, 4), (7, 3, 8)], spans(&automaton, "zoneight"))
+    }
+
+    #[test]
+    fn find_overlapping_reports_nested_patterns() {
+        let automaton = Automaton::new(["he", "she", "his", "hers"]);
+
+        assert_eq!(
+            vec![(1, 1, 4), (0, 2, 4), (3, 2, 6)],
+            spans(&automaton, "ushers")
+        )
+    }
+
+    #[test]
+    fn find_overlapping_with_no_matches_is_empty() {
+        let automaton = Automaton::new(WORDS);
+
+        assert!(spans(&automaton, "xyzzy").is_empty())
+    }
+
+    #[test]
+    fn find_first_prefers_leftmost_start() {
+        let automaton = Automaton::new(["bcd", "abcdef"]);
+
+        let found = automaton.find_first(b"xabcdef").unwrap();
+
+        assert_eq!((1, 1, 7), (found.pattern, found.start, found.end))
+    }
+
+    #[test]
+    fn find_first_and_last_prefer_longest_at_same_start() {
+        let automaton = Automaton::new(["ab", "abc", "a"]);
+
+        assert_eq!(Some(1), automaton.find_first(b"xabcx").map(|m| m.pattern));
+        assert_eq!(Some(1), automaton.find_last(b"xabcx").map(|m| m.pattern))
+    }
+
+    #[test]
+    fn find_last_prefers_rightmost_start() {
+        let automaton = Automaton::new(WORDS);
+
+        let found = automaton.find_last(b"xtwone3four").unwrap();
+        assert_eq!((3, 7, 11), (found.pattern, found.start, found.end));
+
+        let found = automaton.find_last(b"eightwo").unwrap();
+        assert_eq!((1, 4, 7), (found.pattern, found.start, found.end))
+    }
+
+    #[test]
+    fn find_first_and_last_agree_with_overlapping_scan() {
+        let automaton = Automaton::new(WORDS);
+
+        for haystack in [
+            "sevenine",
+            "oneightwoneight",
+            "fivethreeonezblqnsfk1",
+            "abc",
+        ] {
+            let all: Vec<Match> = automaton.find_overlapping(haystack.as_bytes()).collect();
+            let first = all.iter().min_by_key(|m| m.start).copied();
+            let last = all.iter().max_by_key(|m| m.start).copied();
+
+            assert_eq!(first, automaton.find_first(haystack.as_bytes()));
+            assert_eq!(last, automaton.find_last(haystack.as_bytes()))
+        }
+    }
+
+    #[test]
+    fn ascii_case_insensitive_folds_letters_both_ways() {
+        let automaton = Automaton::ascii_case_insensitive(["Seven", "nine"]);
+
+        assert_eq!(vec![(0, 0, 5), (1, 4, 8)], spans(&automaton, "sEVENINE"));
+        assert_eq!(Some(1), automaton.find_last(b"NINE").map(|m| m.pattern));
+        assert!(spans(&Automaton::new(["nine"]), "NINE").is_empty())
+    }
+
+    #[test]
+    fn patterns_may_be_multibyte() {
+        let automaton = Automaton::new(["fünf", "vier"]);
+
+        assert_eq!(vec![(0, 0, 5), (1, 5, 9)], spans(&automaton, "fünfvier"))
+    }
+}
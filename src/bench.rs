@@ -0,0 +1,343 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::unix_timestamp;
+use crate::error::AocError;
+use crate::solver::Entry;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+// Nearest-rank percentile over already sorted samples.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (percent * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        Some(Self {
+            min_ns: nanos(sorted[0]),
+            median_ns: nanos(percentile(&sorted, 50)),
+            p95_ns: nanos(percentile(&sorted, 95)),
+        })
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_ns)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    pub fn p95(&self) -> Duration {
+        Duration::from_nanos(self.p95_ns)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub day: u8,
+    pub part: u8,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+pub fn bench_entry(entry: &Entry, input: &[u8], runs: usize) -> Result<BenchResult, AocError> {
+    let runs = runs.max(1);
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = Vec::with_capacity(runs);
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let solver = entry.parse(&mut &input[..])?;
+        parse_samples.push(start.elapsed());
+
+        let start = Instant::now();
+        let answer = entry.solve_parsed(solver.as_ref())?;
+        solve_samples.push(start.elapsed());
+
+        std::hint::black_box(answer);
+    }
+
+    Ok(BenchResult {
+        day: entry.day,
+        part: entry.part,
+        parse: Stats::from_samples(&parse_samples).expect("at least one run"),
+        solve: Stats::from_samples(&solve_samples).expect("at least one run"),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Parse,
+    Solve,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub part: u8,
+    pub phase: Phase,
+    pub previous: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    // None when the previous median was too fast for the timer to see, since
+    // any slowdown from zero is unbounded.
+    pub fn slowdown_percent(&self) -> Option<f64> {
+        let previous = self.previous.as_secs_f64();
+        (previous > 0.0).then(|| (self.current.as_secs_f64() - previous) / previous * 100.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BenchRun {
+    pub timestamp: u64,
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchRun {
+    pub fn now(results: Vec<BenchResult>) -> Self {
+        Self {
+            timestamp: unix_timestamp(),
+            results,
+        }
+    }
+
+    fn find(&self, day: u8, part: u8) -> Option<&BenchResult> {
+        self.results.iter().find(|r| r.day == day && r.part == part)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct History {
+    pub runs: Vec<BenchRun>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| AocError::History(e.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(AocError::from(e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AocError> {
+        let text =
+            serde_json::to_string_pretty(self).map_err(|e| AocError::History(e.to_string()))?;
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    pub fn latest(&self) -> Option<&BenchRun> {
+        self.runs.last()
+    }
+
+    // The newest timing of a day and part, from whichever run last benched it.
+    pub fn previous(&self, day: u8, part: u8) -> Option<&BenchResult> {
+        self.runs.iter().rev().find_map(|run| run.find(day, part))
+    }
+
+    // Compares medians, which are far less noisy than min or p95 for the
+    // handful of runs a benchmark usually gets.
+    pub fn regressions(&self, run: &BenchRun, threshold: f64) -> Vec<Regression> {
+        let mut regressions = vec![];
+
+        for current in run.results.iter() {
+            let Some(before) = self.previous(current.day, current.part) else {
+                continue;
+            };
+
+            let phases = [
+                (Phase::Parse, before.parse, current.parse),
+                (Phase::Solve, before.solve, current.solve),
+            ];
+            for (phase, before, after) in phases {
+                if after.median_ns as f64 > before.median_ns as f64 * (1.0 + threshold) {
+                    regressions.push(Regression {
+                        day: current.day,
+                        part: current.part,
+                        phase,
+                        previous: before.median(),
+                        current: after.median(),
+                    });
+                }
+            }
+        }
+
+        regressions
+    }
+
+    pub fn record(&mut self, run: BenchRun) {
+        self.runs.push(run);
+    }
+}
+
+#[cfg(test)]
+mod bench_tests {
+    use super::*;
+    use crate::registry;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn result(day: u8, part: u8, parse_ms: u64, solve_ms: u64) -> BenchResult {
+        let stats = |n: u64| Stats::from_samples(&[ms(n)]).unwrap();
+        BenchResult {
+            day,
+            part,
+            parse: stats(parse_ms),
+            solve: stats(solve_ms),
+        }
+    }
+
+    #[test]
+    fn stats_from_samples_uses_nearest_rank() {
+        let samples: Vec<Duration> = (1..=20).rev().map(ms).collect();
+
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(ms(1), stats.min());
+        assert_eq!(ms(10), stats.median());
+        assert_eq!(ms(19), stats.p95())
+    }
+
+    #[test]
+    fn stats_from_single_sample_is_that_sample() {
+        let stats = Stats::from_samples(&[ms(7)]).unwrap();
+
+        assert_eq!(ms(7), stats.min());
+        assert_eq!(ms(7), stats.median());
+        assert_eq!(ms(7), stats.p95())
+    }
+
+    #[test]
+    fn stats_from_no_samples_is_none() {
+        assert_eq!(None, Stats::from_samples(&[]))
+    }
+
+    fn history(runs: Vec<Vec<BenchResult>>) -> History {
+        History {
+            runs: runs.into_iter().map(BenchRun::now).collect(),
+        }
+    }
+
+    #[test]
+    fn regressions_flag_slower_medians_over_threshold() {
+        let history = history(vec![vec![result(1, 1, 10, 10), result(1, 2, 10, 10)]]);
+        let current = BenchRun::now(vec![result(1, 1, 10, 12), result(1, 2, 11, 10)]);
+
+        let regressions = history.regressions(&current, 0.1);
+
+        assert_eq!(
+            vec![Regression {
+                day: 1,
+                part: 1,
+                phase: Phase::Solve,
+                previous: ms(10),
+                current: ms(12),
+            }],
+            regressions
+        );
+        assert!((regressions[0].slowdown_percent().unwrap() - 20.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn slowdown_from_a_zero_median_has_no_percentage() {
+        let regression = Regression {
+            day: 1,
+            part: 1,
+            phase: Phase::Solve,
+            previous: Duration::ZERO,
+            current: ms(1),
+        };
+
+        assert_eq!(None, regression.slowdown_percent())
+    }
+
+    #[test]
+    fn regressions_compare_with_the_last_run_of_each_entry() {
+        let history = history(vec![
+            vec![result(1, 1, 20, 20), result(2, 1, 10, 10)],
+            vec![result(1, 1, 10, 10)],
+            vec![result(3, 1, 10, 10)],
+        ]);
+        let current = BenchRun::now(vec![result(1, 1, 15, 10), result(2, 1, 10, 15)]);
+
+        let regressions: Vec<(u8, Phase, Duration)> = history
+            .regressions(&current, 0.1)
+            .iter()
+            .map(|r| (r.day, r.phase, r.previous))
+            .collect();
+
+        assert_eq!(
+            vec![(1, Phase::Parse, ms(10)), (2, Phase::Solve, ms(10))],
+            regressions
+        )
+    }
+
+    #[test]
+    fn entries_never_benched_before_have_no_previous_timing() {
+        let history = history(vec![vec![result(1, 1, 10, 10)]]);
+        let current = BenchRun::now(vec![result(2, 1, 100, 100)]);
+
+        assert!(history.previous(2, 1).is_none());
+        assert!(history.regressions(&current, 0.1).is_empty())
+    }
+
+    #[test]
+    fn history_round_trips_through_json() {
+        let mut history = History::default();
+        history.record(BenchRun {
+            timestamp: 1_700_000_000,
+            results: vec![result(3, 2, 1, 2)],
+        });
+
+        let text = serde_json::to_string(&history).unwrap();
+        let loaded: History = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(history, loaded)
+    }
+
+    #[test]
+    fn load_missing_history_is_empty() {
+        let history = History::load(Path::new("does/not/exist.json")).unwrap();
+
+        assert!(history.latest().is_none())
+    }
+
+    #[test]
+    fn bench_entry_runs_registered_solver() {
+        let registry = registry();
+        let entry = registry.get(1, 1).unwrap();
+
+        let result = bench_entry(entry, b"1abc2\npqr3stu8vwx", 3).unwrap();
+
+        assert_eq!((1, 1), (result.day, result.part));
+        assert!(result.parse.min() <= result.parse.p95())
+    }
+}
@@ -0,0 +1,686 @@
+use std::path::PathBuf;
+
+use adventofcode_2023::Bag;
+
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+
+    pub fn default_for_day(day: u8) -> Self {
+        InputSource::Path(PathBuf::from(format!("inputs/day_{}.txt", day)))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunArgs {
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub input: Option<InputSource>,
+    pub all: bool,
+    pub bag: Option<Bag>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyArgs {
+    pub manifest: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchArgs {
+    pub day: Option<u8>,
+    pub runs: usize,
+    pub history: PathBuf,
+    pub threshold: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FetchArgs {
+    pub day: u8,
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    pub dir: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SubmitArgs {
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<InputSource>,
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    pub log: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExplainArgs {
+    pub part: u8,
+    pub input: InputSource,
+    pub brackets: bool,
+    pub strict: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenerateArgs {
+    pub seed: u64,
+    pub lines: usize,
+    pub overlap: f64,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunArgs),
+    Verify(VerifyArgs),
+    Bench(BenchArgs),
+    Fetch(FetchArgs),
+    Submit(SubmitArgs),
+    Explain(ExplainArgs),
+    Generate(GenerateArgs),
+    Help,
+}
+
+pub const USAGE: &str = "usage:
+    aoc run --day <N> [--part <1|2>] [--input <path|->]
+    aoc run --day 2 [--part 1] --bag <bag> [--input <path|->]
+    aoc run --all
+    aoc verify [--manifest <path>]
+    aoc bench [--day <N>] [--runs <N>] [--history <path>] [--threshold <percent>]
+    aoc fetch --day <N> [--session <token>] [--base-url <url>] [--dir <path>]
+    aoc submit --day <N> --part <1|2> [--input <path|->] [--session <token>]
+               [--base-url <url>] [--log <path>]
+    aoc explain [--part <1|2>] [--input <path|->] [--brackets] [--strict]
+    aoc generate [--seed <N>] [--lines <N>] [--overlap <0-1>] [--output <path>]
+
+fetch and submit read the session token from AOC_SESSION and the base url
+from AOC_BASE_URL when the flags are not given. A bag is written like the
+cubes of a round, for example \"12 red, 13 green, 14 blue\".";
+
+fn next_value<I>(flag: &str, args: &mut I) -> Result<String, String>
+where
+    I: Iterator<Item = String>,
+{
+    args.next()
+        .ok_or_else(|| format!("missing value for {}", flag))
+}
+
+fn parse_number(flag: &str, value: &str) -> Result<u8, String> {
+    value
+        .parse::<u8>()
+        .map_err(|_| format!("invalid value for {}: {}", flag, value))
+}
+
+fn parse_run<I>(mut args: I) -> Result<RunArgs, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut run = RunArgs {
+        day: None,
+        part: None,
+        input: None,
+        all: false,
+        bag: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let value = next_value(&arg, &mut args)?;
+                run.day = Some(parse_number(&arg, &value)?);
+            }
+            "--part" => {
+                let value = next_value(&arg, &mut args)?;
+                run.part = Some(parse_number(&arg, &value)?);
+            }
+            "--input" => {
+                let value = next_value(&arg, &mut args)?;
+                run.input = Some(InputSource::from_arg(&value));
+            }
+            "--all" => run.all = true,
+            "--bag" => {
+                let value = next_value(&arg, &mut args)?;
+                let bag = value
+                    .parse::<Bag>()
+                    .map_err(|e| format!("invalid value for {}: {}", arg, e))?;
+                run.bag = Some(bag);
+            }
+            other => return Err(format!("unknown argument: {}", other)),
+        }
+    }
+
+    if let Some(part) = run.part {
+        if part != 1 && part != 2 {
+            return Err(format!("part must be 1 or 2, got {}", part));
+        }
+    }
+
+    if run.bag.is_some() && (run.day != Some(2) || run.part == Some(2)) {
+        return Err(String::from("--bag only applies to day 2 part 1"));
+    }
+
+    match (run.all, run.day) {
+        (true, Some(_)) => Err(String::from("--all cannot be combined with --day")),
+        (true, None) if run.input.is_some() => Err(String::from(
+            "--all reads inputs/day_N.txt and cannot take --input",
+        )),
+        (false, None) => Err(String::from("either --day or --all is required")),
+        _ => Ok(run),
+    }
+}
+
+fn parse_verify<I>(mut args: I) -> Result<VerifyArgs, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut verify = VerifyArgs {
+        manifest: PathBuf::from("answers.toml"),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--manifest" => verify.manifest = PathBuf::from(next_value(&arg, &mut args)?),
+            other => return Err(format!("unknown argument: {}", other)),
+        }
+    }
+
+    Ok(verify)
+}
+
+fn parse_bench<I>(mut args: I) -> Result<BenchArgs, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut bench = BenchArgs {
+        day: None,
+        runs: 10,
+        history: PathBuf::from("bench_history.json"),
+        threshold: 10.0,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let value = next_value(&arg, &mut args)?;
+                bench.day = Some(parse_number(&arg, &value)?);
+            }
+            "--runs" => {
+                let value = next_value(&arg, &mut args)?;
+                bench.runs = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|runs| *runs > 0)
+                    .ok_or_else(|| format!("invalid value for {}: {}", arg, value))?;
+            }
+            "--history" => bench.history = PathBuf::from(next_value(&arg, &mut args)?),
+            "--threshold" => {
+                let value = next_value(&arg, &mut args)?;
+                bench.threshold = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|threshold| *threshold >= 0.0)
+                    .ok_or_else(|| format!("invalid value for {}: {}", arg, value))?;
+            }
+            other => return Err(format!("unknown argument: {}", other)),
+        }
+    }
+
+    Ok(bench)
+}
+
+fn parse_fetch<I>(mut args: I) -> Result<FetchArgs, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut day = None;
+    let mut session = None;
+    let mut base_url = None;
+    let mut dir = PathBuf::from("inputs");
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let value = next_value(&arg, &mut args)?;
+                day = Some(parse_number(&arg, &value)?);
+            }
+            "--session" => session = Some(next_value(&arg, &mut args)?),
+            "--base-url" => base_url = Some(next_value(&arg, &mut args)?),
+            "--dir" => dir = PathBuf::from(next_value(&arg, &mut args)?),
+            other => return Err(format!("unknown argument: {}", other)),
+        }
+    }
+
+    let day = day.ok_or_else(|| String::from("--day is required"))?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("day must be between 1 and 25, got {}", day));
+    }
+
+    Ok(FetchArgs {
+        day,
+        session,
+        base_url,
+        dir,
+    })
+}
+
+fn parse_submit<I>(mut args: I) -> Result<SubmitArgs, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut day = None;
+    let mut part = None;
+    let mut submit = SubmitArgs {
+        day: 0,
+        part: 0,
+        input: None,
+        session: None,
+        base_url: None,
+        log: PathBuf::from("submissions.json"),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let value = next_value(&arg, &mut args)?;
+                day = Some(parse_number(&arg, &value)?);
+            }
+            "--part" => {
+                let value = next_value(&arg, &mut args)?;
+                part = Some(parse_number(&arg, &value)?);
+            }
+            "--input" => {
+                let value = next_value(&arg, &mut args)?;
+                submit.input = Some(InputSource::from_arg(&value));
+            }
+            "--session" => submit.session = Some(next_value(&arg, &mut args)?),
+            "--base-url" => submit.base_url = Some(next_value(&arg, &mut args)?),
+            "--log" => submit.log = PathBuf::from(next_value(&arg, &mut args)?),
+            other => return Err(format!("unknown argument: {}", other)),
+        }
+    }
+
+    submit.day = day.ok_or_else(|| String::from("--day is required"))?;
+    submit.part = part.ok_or_else(|| String::from("--part is required"))?;
+    if submit.part != 1 && submit.part != 2 {
+        return Err(format!("part must be 1 or 2, got {}", submit.part));
+    }
+
+    Ok(submit)
+}
+
+fn parse_explain<I>(mut args: I) -> Result<ExplainArgs, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut explain = ExplainArgs {
+        part: 2,
+        input: InputSource::default_for_day(1),
+        brackets: false,
+        strict: false,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = next_value(&arg, &mut args)?;
+                explain.part = parse_number(&arg, &value)?;
+            }
+            "--input" => {
+                let value = next_value(&arg, &mut args)?;
+                explain.input = InputSource::from_arg(&value);
+            }
+            "--brackets" => explain.brackets = true,
+            "--strict" => explain.strict = true,
+            other => return Err(format!("unknown argument: {}", other)),
+        }
+    }
+
+    if explain.part != 1 && explain.part != 2 {
+        return Err(format!("part must be 1 or 2, got {}", explain.part));
+    }
+
+    Ok(explain)
+}
+
+fn parse_generate<I>(mut args: I) -> Result<GenerateArgs, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut generate = GenerateArgs {
+        seed: 2023,
+        lines: 1000,
+        overlap: 0.25,
+        output: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = next_value(&arg, &mut args)?;
+                generate.seed = value
+                    .parse::<u64>()
+                    .map_err(|_| format!("invalid value for {}: {}", arg, value))?;
+            }
+            "--lines" => {
+                let value = next_value(&arg, &mut args)?;
+                generate.lines = value
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid value for {}: {}", arg, value))?;
+            }
+            "--overlap" => {
+                let value = next_value(&arg, &mut args)?;
+                generate.overlap = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|overlap| (0.0..=1.0).contains(overlap))
+                    .ok_or_else(|| format!("invalid value for {}: {}", arg, value))?;
+            }
+            "--output" => generate.output = Some(PathBuf::from(next_value(&arg, &mut args)?)),
+            other => return Err(format!("unknown argument: {}", other)),
+        }
+    }
+
+    Ok(generate)
+}
+
+pub fn parse<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("verify") => parse_verify(args).map(Command::Verify),
+        Some("bench") => parse_bench(args).map(Command::Bench),
+        Some("fetch") => parse_fetch(args).map(Command::Fetch),
+        Some("submit") => parse_submit(args).map(Command::Submit),
+        Some("explain") => parse_explain(args).map(Command::Explain),
+        Some("generate") => parse_generate(args).map(Command::Generate),
+        Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+        Some(other) => Err(format!("unknown command: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod args_tests {
+    use super::*;
+
+    fn args(input: &str) -> Vec<String> {
+        input.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_run_with_day_part_and_file() {
+        let command = parse(args("run --day 3 --part 2 --input inputs/day_3.txt"));
+
+        let expected = Command::Run(RunArgs {
+            day: Some(3),
+            part: Some(2),
+            input: Some(InputSource::Path(PathBuf::from("inputs/day_3.txt"))),
+            all: false,
+            bag: None,
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_run_with_stdin_input() {
+        let command = parse(args("run --day 1 --input -"));
+
+        let expected = Command::Run(RunArgs {
+            day: Some(1),
+            part: None,
+            input: Some(InputSource::Stdin),
+            all: false,
+            bag: None,
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_run_all() {
+        let command = parse(args("run --all"));
+
+        let expected = Command::Run(RunArgs {
+            day: None,
+            part: None,
+            input: None,
+            all: true,
+            bag: None,
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_run_with_bag() {
+        let mut input = args("run --day 2 --bag");
+        input.push(String::from("12 red, 13 green, 14 blue"));
+
+        let expected = Command::Run(RunArgs {
+            day: Some(2),
+            part: None,
+            input: None,
+            all: false,
+            bag: Some("12 red, 13 green, 14 blue".parse().unwrap()),
+        });
+
+        assert_eq!(Ok(expected), parse(input))
+    }
+
+    #[test]
+    fn parse_verify_defaults_to_answers_toml() {
+        let command = parse(args("verify"));
+
+        let expected = Command::Verify(VerifyArgs {
+            manifest: PathBuf::from("answers.toml"),
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_verify_with_manifest() {
+        let command = parse(args("verify --manifest other/answers.toml"));
+
+        let expected = Command::Verify(VerifyArgs {
+            manifest: PathBuf::from("other/answers.toml"),
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_bench_defaults() {
+        let command = parse(args("bench"));
+
+        let expected = Command::Bench(BenchArgs {
+            day: None,
+            runs: 10,
+            history: PathBuf::from("bench_history.json"),
+            threshold: 10.0,
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_bench_with_options() {
+        let command = parse(args(
+            "bench --day 2 --runs 50 --history h.json --threshold 5",
+        ));
+
+        let expected = Command::Bench(BenchArgs {
+            day: Some(2),
+            runs: 50,
+            history: PathBuf::from("h.json"),
+            threshold: 5.0,
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_fetch_with_defaults() {
+        let command = parse(args("fetch --day 5"));
+
+        let expected = Command::Fetch(FetchArgs {
+            day: 5,
+            session: None,
+            base_url: None,
+            dir: PathBuf::from("inputs"),
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_fetch_with_options() {
+        let command = parse(args(
+            "fetch --day 5 --session abc --base-url http://localhost:8080 --dir fixtures",
+        ));
+
+        let expected = Command::Fetch(FetchArgs {
+            day: 5,
+            session: Some(String::from("abc")),
+            base_url: Some(String::from("http://localhost:8080")),
+            dir: PathBuf::from("fixtures"),
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_submit_with_options() {
+        let command = parse(args(
+            "submit --day 4 --part 2 --input - --base-url http://localhost:8080 --log log.json",
+        ));
+
+        let expected = Command::Submit(SubmitArgs {
+            day: 4,
+            part: 2,
+            input: Some(InputSource::Stdin),
+            session: None,
+            base_url: Some(String::from("http://localhost:8080")),
+            log: PathBuf::from("log.json"),
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_explain_defaults_to_part_2_of_day_1() {
+        let command = parse(args("explain"));
+
+        let expected = Command::Explain(ExplainArgs {
+            part: 2,
+            input: InputSource::Path(PathBuf::from("inputs/day_1.txt")),
+            brackets: false,
+            strict: false,
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_explain_with_options() {
+        let command = parse(args("explain --part 1 --input - --brackets --strict"));
+
+        let expected = Command::Explain(ExplainArgs {
+            part: 1,
+            input: InputSource::Stdin,
+            brackets: true,
+            strict: true,
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_generate_defaults() {
+        let command = parse(args("generate"));
+
+        let expected = Command::Generate(GenerateArgs {
+            seed: 2023,
+            lines: 1000,
+            overlap: 0.25,
+            output: None,
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_generate_with_options() {
+        let command = parse(args(
+            "generate --seed 7 --lines 100000 --overlap 0.9 --output big.txt",
+        ));
+
+        let expected = Command::Generate(GenerateArgs {
+            seed: 7,
+            lines: 100000,
+            overlap: 0.9,
+            output: Some(PathBuf::from("big.txt")),
+        });
+
+        assert_eq!(Ok(expected), command)
+    }
+
+    #[test]
+    fn parse_run_with_bag_errors() {
+        let run_with_bag = |command: &str, bag: &str| {
+            let mut input = args(command);
+            input.extend([String::from("--bag"), String::from(bag)]);
+            parse(input)
+        };
+
+        assert_eq!(
+            Err(String::from("--bag only applies to day 2 part 1")),
+            run_with_bag("run --day 1", "12 red")
+        );
+        assert!(run_with_bag("run --day 2 --part 2", "12 red").is_err());
+        assert!(run_with_bag("run --day 2", "red").is_err())
+    }
+
+    macro_rules! parse_error_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let input = $value;
+                    assert!(parse(args(input)).is_err())
+                }
+            )*
+        }
+    }
+
+    parse_error_tests! {
+        run_without_day_is_error: "run",
+        run_with_invalid_part_is_error: "run --day 1 --part 3",
+        run_with_missing_value_is_error: "run --day",
+        run_with_non_numeric_day_is_error: "run --day three",
+        run_all_with_day_is_error: "run --all --day 1",
+        unknown_command_is_error: "solve",
+        verify_with_unknown_argument_is_error: "verify --day 1",
+        bench_with_zero_runs_is_error: "bench --runs 0",
+        bench_with_negative_threshold_is_error: "bench --threshold -1",
+        fetch_without_day_is_error: "fetch",
+        fetch_with_day_out_of_range_is_error: "fetch --day 26",
+        submit_without_part_is_error: "submit --day 1",
+        submit_with_invalid_part_is_error: "submit --day 1 --part 3",
+        explain_with_invalid_part_is_error: "explain --part 0",
+        generate_with_overlap_above_one_is_error: "generate --overlap 1.5",
+        generate_with_negative_seed_is_error: "generate --seed -1",
+    }
+}
@@ -0,0 +1,368 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use adventofcode_2023::{
+    bench_entry, check_day_2_bag, explain_day_1, registry, submit, AttemptLog, Bag, BenchRun,
+    CalibrationGenerator, CalibrationReport, Client, DigitVocabulary, Entry, Highlight, History,
+    InputCache, Manifest, Outcome, Registry, Stats, Verdict,
+};
+
+use crate::args::{
+    BenchArgs, Command, ExplainArgs, FetchArgs, GenerateArgs, InputSource, RunArgs, SubmitArgs,
+    VerifyArgs, USAGE,
+};
+
+mod args;
+
+fn open_input(source: &InputSource) -> Result<Box<dyn BufRead>, String> {
+    match source {
+        InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+        InputSource::Path(path) => File::open(path)
+            .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e)),
+    }
+}
+
+fn run_day(
+    registry: &Registry,
+    day: u8,
+    part: Option<u8>,
+    source: &InputSource,
+) -> Result<(), String> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let entries = parts
+        .iter()
+        .map(|part| {
+            registry
+                .get(day, *part)
+                .ok_or_else(|| format!("day {} part {} is not implemented", day, part))
+        })
+        .collect::<Result<Vec<&Entry>, String>>()?;
+
+    let mut input = open_input(source)?;
+
+    let start = Instant::now();
+    let solver = entries[0]
+        .parse(&mut input)
+        .map_err(|e| format!("day {}: {}", day, e))?;
+    println!("day {} parsed ({:.3?})", day, start.elapsed());
+
+    for entry in entries {
+        let start = Instant::now();
+        let answer = entry
+            .solve_parsed(solver.as_ref())
+            .map_err(|e| format!("day {} part {}: {}", day, entry.part, e))?;
+        let elapsed = start.elapsed();
+
+        println!(
+            "day {} part {}: {} ({:.3?})",
+            entry.day, entry.part, answer, elapsed
+        );
+    }
+
+    Ok(())
+}
+
+fn run_bag(bag: &Bag, source: &InputSource) -> Result<(), String> {
+    let report = check_day_2_bag(open_input(source)?, bag).map_err(|e| format!("day 2: {}", e))?;
+
+    for (id, shortfalls) in report.impossible.iter() {
+        let shortfalls: Vec<String> = shortfalls.iter().map(|s| s.to_string()).collect();
+        println!("game {} is impossible: {}", id, shortfalls.join(", "));
+    }
+    println!(
+        "{} of {} games are possible with {}",
+        report.possible.len(),
+        report.possible.len() + report.impossible.len(),
+        bag
+    );
+    println!("day 2 part 1: {}", report.id_sum());
+
+    Ok(())
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let registry = registry();
+
+    if args.all {
+        for day in registry.days() {
+            run_day(
+                &registry,
+                day,
+                args.part,
+                &InputSource::default_for_day(day),
+            )?;
+        }
+        return Ok(());
+    }
+
+    let day = args.day.ok_or_else(|| String::from("--day is required"))?;
+    let source = args
+        .input
+        .unwrap_or_else(|| InputSource::default_for_day(day));
+
+    match args.bag {
+        Some(bag) => run_bag(&bag, &source),
+        None => run_day(&registry, day, args.part, &source),
+    }
+}
+
+fn verify(args: VerifyArgs) -> Result<(), String> {
+    let manifest = Manifest::load(&args.manifest)
+        .map_err(|e| format!("{}: {}", args.manifest.display(), e))?;
+
+    let results = manifest.verify(&registry());
+    for result in results.iter() {
+        let answer = result.answer;
+        let status = match &result.outcome {
+            Outcome::Correct(actual) => format!("ok {}", actual),
+            Outcome::Wrong(actual) => {
+                format!("WRONG expected {} got {}", answer.expected(), actual)
+            }
+            Outcome::NotImplemented => String::from("NOT IMPLEMENTED"),
+            Outcome::Failed(e) => format!("FAILED {}", e),
+        };
+
+        println!(
+            "day {} part {} ({}): {}",
+            answer.day,
+            answer.part,
+            answer.input.display(),
+            status
+        );
+    }
+
+    let failed = results.iter().filter(|r| !r.passed()).count();
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} of {} answers failed", n, results.len())),
+    }
+}
+
+fn format_stats(stats: &Stats) -> String {
+    format!(
+        "min {:>10.3?} median {:>10.3?} p95 {:>10.3?}",
+        stats.min(),
+        stats.median(),
+        stats.p95()
+    )
+}
+
+fn bench(args: BenchArgs) -> Result<(), String> {
+    let registry = registry();
+    let mut history =
+        History::load(&args.history).map_err(|e| format!("{}: {}", args.history.display(), e))?;
+
+    let mut results = vec![];
+    for entry in registry.entries() {
+        if args.day.is_some_and(|day| day != entry.day) {
+            continue;
+        }
+
+        let path = format!("inputs/day_{}.txt", entry.day);
+        let input = fs::read(&path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+        let result = bench_entry(entry, &input, args.runs)
+            .map_err(|e| format!("day {} part {}: {}", entry.day, entry.part, e))?;
+
+        println!("day {} part {}", result.day, result.part);
+        println!("    parse  {}", format_stats(&result.parse));
+        println!("    solve  {}", format_stats(&result.solve));
+        results.push(result);
+    }
+
+    let run = BenchRun::now(results);
+    for result in run.results.iter() {
+        if history.previous(result.day, result.part).is_none() {
+            println!(
+                "day {} part {} has no earlier timing to compare with",
+                result.day, result.part
+            );
+        }
+    }
+    for regression in history.regressions(&run, args.threshold / 100.0) {
+        let slowdown = match regression.slowdown_percent() {
+            Some(percent) => format!("+{:.1}%", percent),
+            None => String::from("up from zero"),
+        };
+        println!(
+            "REGRESSION day {} part {} {:?}: median {:.3?} -> {:.3?} ({})",
+            regression.day,
+            regression.part,
+            regression.phase,
+            regression.previous,
+            regression.current,
+            slowdown
+        );
+    }
+
+    history.record(run);
+    history
+        .save(&args.history)
+        .map_err(|e| format!("{}: {}", args.history.display(), e))
+}
+
+fn client(session: Option<String>, base_url: Option<String>) -> Result<Client, String> {
+    let client = match session {
+        Some(session) => Client::new(session),
+        None => Client::from_env().map_err(|e| e.to_string())?,
+    };
+
+    Ok(match base_url {
+        Some(base_url) => client.with_base_url(base_url),
+        None => client,
+    })
+}
+
+fn fetch(args: FetchArgs) -> Result<(), String> {
+    let client = client(args.session, args.base_url)?;
+
+    let cache = InputCache::new(&args.dir);
+    let (path, fetched) = cache
+        .fetch(&client, args.day)
+        .map_err(|e| format!("day {}: {}", args.day, e))?;
+
+    match fetched {
+        true => println!("day {}: downloaded to {}", args.day, path.display()),
+        false => println!("day {}: already cached at {}", args.day, path.display()),
+    }
+
+    Ok(())
+}
+
+fn submit_answer(args: SubmitArgs) -> Result<(), String> {
+    let registry = registry();
+    let entry = registry
+        .get(args.day, args.part)
+        .ok_or_else(|| format!("day {} part {} is not implemented", args.day, args.part))?;
+
+    let source = args
+        .input
+        .unwrap_or_else(|| InputSource::default_for_day(args.day));
+    let answer = entry
+        .solve(&mut open_input(&source)?)
+        .map_err(|e| format!("day {}: {}", args.day, e))?;
+
+    let log_error = |e| format!("{}: {}", args.log.display(), e);
+    let mut log = AttemptLog::load(&args.log).map_err(log_error)?;
+    if let Some(refusal) = log.check(args.day, args.part, &answer) {
+        return Err(format!("refusing to submit {}: {}", answer, refusal));
+    }
+
+    let client = client(args.session, args.base_url)?;
+    let verdict = submit(&client, args.day, args.part, &answer)
+        .map_err(|e| format!("day {} part {}: {}", args.day, args.part, e))?;
+    println!(
+        "day {} part {}: submitted {}: {}",
+        args.day, args.part, answer, verdict
+    );
+
+    log.record(args.day, args.part, &answer, verdict.clone());
+    log.save(&args.log).map_err(log_error)?;
+
+    match verdict {
+        Verdict::Correct | Verdict::AlreadySolved => Ok(()),
+        _ => Err(format!("{} was not accepted", answer)),
+    }
+}
+
+fn explain(args: ExplainArgs) -> Result<(), String> {
+    let vocabulary = match args.part {
+        1 => DigitVocabulary::digits_only(),
+        _ => DigitVocabulary::english(),
+    };
+    let highlight = match args.brackets {
+        true => Highlight::Brackets,
+        false => Highlight::Ansi,
+    };
+
+    let explanations = explain_day_1(open_input(&args.input)?, &vocabulary)
+        .map_err(|e| format!("day 1: {}", e))?;
+
+    let mut report = CalibrationReport::default();
+    for (i, explanation) in explanations.iter().enumerate() {
+        println!(
+            "{:>5}: {} -> {}",
+            i + 1,
+            explanation.render(highlight),
+            explanation.value
+        );
+        report.add(i + 1, explanation);
+    }
+    println!("total: {}", report.total);
+
+    if report.is_clean() {
+        return Ok(());
+    }
+
+    let missing: Vec<String> = report.missing.iter().map(|l| l.to_string()).collect();
+    println!("lines without a digit: {}", missing.join(", "));
+    match args.strict {
+        true => Err(format!("{} lines without a digit", missing.len())),
+        false => Ok(()),
+    }
+}
+
+// Writes a day 1 document with known answers. The answers go to stderr when
+// the document itself is written to stdout.
+fn generate(args: GenerateArgs) -> Result<(), String> {
+    let document = CalibrationGenerator::new(args.seed)
+        .with_lines(args.lines)
+        .with_overlap_density(args.overlap)
+        .generate();
+    let answers = format!(
+        "day 1 part 1: {}\nday 1 part 2: {}",
+        document.part_1, document.part_2
+    );
+
+    match args.output {
+        Some(path) => {
+            fs::write(&path, document.text())
+                .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+            println!("{}", answers);
+        }
+        None => {
+            print!("{}", document.text());
+            eprintln!("{}", answers);
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let command = match args::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match command {
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Bench(args) => bench(args),
+        Command::Fetch(args) => fetch(args),
+        Command::Submit(args) => submit_answer(args),
+        Command::Explain(args) => explain(args),
+        Command::Generate(args) => generate(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use crate::document::Document;
+use crate::rng::Rng;
+
+const WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+// None of these letters appear in a number word, so filler can never start
+// or finish one by accident.
+const FILLER: &[u8] = b"abcdjklmpqyz";
+
+// "two", "one" and "eight" overlap in a cycle, so chains need a cap.
+const MAX_CHAIN: usize = 4;
+
+// Builds day 1 documents token by token, so the expected sums are known from
+// construction rather than by running any of the solvers being checked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationGenerator {
+    seed: u64,
+    lines: usize,
+    overlap_density: f64,
+}
+
+// Words `a` and `b` where `b` can start on the last `shared` letters of `a`,
+// as in "eightwo".
+struct Overlap {
+    a: usize,
+    b: usize,
+    shared: usize,
+}
+
+fn overlaps() -> Vec<Overlap> {
+    let mut overlaps = vec![];
+
+    for (a, (first, _)) in WORDS.iter().enumerate() {
+        for (b, (second, _)) in WORDS.iter().enumerate() {
+            for shared in 1..first.len().min(second.len()) {
+                if first[first.len() - shared..] == second[..shared] {
+                    overlaps.push(Overlap { a, b, shared });
+                }
+            }
+        }
+    }
+
+    overlaps
+}
+
+struct Line {
+    text: String,
+    digits: Vec<u32>,
+    values: Vec<u32>,
+}
+
+impl Line {
+    fn filler(&mut self, rng: &mut Rng, min: usize, max: usize) {
+        let count = min + rng.below(max - min + 1);
+        for _ in 0..count {
+            self.text.push(*rng.choose(FILLER) as char);
+        }
+    }
+
+    fn calibration_value(values: &[u32]) -> u64 {
+        match (values.first(), values.last()) {
+            (Some(first), Some(last)) => (first * 10 + last) as u64,
+            _ => 0,
+        }
+    }
+}
+
+impl CalibrationGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            lines: 1000,
+            overlap_density: 0.25,
+        }
+    }
+
+    pub fn with_lines(self, lines: usize) -> Self {
+        Self { lines, ..self }
+    }
+
+    // The chance that a number word runs straight into another one that
+    // shares its letters, like "oneight", instead of ending cleanly.
+    pub fn with_overlap_density(self, overlap_density: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&overlap_density),
+            "overlap density must be between 0 and 1, got {}",
+            overlap_density
+        );
+        Self {
+            overlap_density,
+            ..self
+        }
+    }
+
+    pub fn generate(&self) -> Document {
+        let mut rng = Rng::new(self.seed);
+        let overlaps = overlaps();
+
+        let mut document = Document::with_capacity(self.lines);
+
+        for _ in 0..self.lines {
+            let line = self.line(&mut rng, &overlaps);
+            document.part_1 += Line::calibration_value(&line.digits);
+            document.part_2 += Line::calibration_value(&line.values);
+            document.lines.push(line.text);
+        }
+
+        document
+    }
+
+    fn line(&self, rng: &mut Rng, overlaps: &[Overlap]) -> Line {
+        let mut line = Line {
+            text: String::new(),
+            digits: vec![],
+            values: vec![],
+        };
+
+        let tokens = 1 + rng.below(6);
+        line.filler(rng, 0, 4);
+        for token in 0..tokens {
+            if token > 0 {
+                line.filler(rng, 1, 3);
+            }
+
+            if rng.below(3) == 0 {
+                let digit = 1 + rng.below(9) as u32;
+                line.text
+                    .push(char::from_digit(digit, 10).expect("1-9 is a digit"));
+                line.digits.push(digit);
+                line.values.push(digit);
+                continue;
+            }
+
+            let mut word = rng.below(WORDS.len());
+            line.text.push_str(WORDS[word].0);
+            line.values.push(WORDS[word].1);
+
+            for _ in 0..MAX_CHAIN {
+                if !rng.chance(self.overlap_density) {
+                    break;
+                }
+
+                let next: Vec<&Overlap> = overlaps.iter().filter(|o| o.a == word).collect();
+                if next.is_empty() {
+                    break;
+                }
+
+                let overlap = *rng.choose(&next);
+                let (text, value) = WORDS[overlap.b];
+                line.text.push_str(&text[overlap.shared..]);
+                line.values.push(value);
+                word = overlap.b;
+            }
+        }
+        line.filler(rng, 0, 4);
+
+        line
+    }
+}
+
+#[cfg(test)]
+mod calibration_generator_tests {
+    use super::*;
+    use crate::document::document_checks::{assert_answers, assert_seeded};
+    use crate::extractor::NaiveExtractor;
+    use crate::vocabulary::DigitVocabulary;
+    use crate::{solve_day_1_part_1, solve_day_1_part_2};
+
+    fn has_overlapping_words(extractor: &NaiveExtractor, line: &str) -> bool {
+        extractor
+            .matches(line)
+            .windows(2)
+            .any(|pair| pair[0].end > pair[1].start)
+    }
+
+    macro_rules! generated_answer_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (seed, density) = $value;
+                    let document = CalibrationGenerator::new(seed)
+                        .with_lines(500)
+                        .with_overlap_density(density)
+                        .generate();
+
+                    assert_answers(
+                        &document,
+                        |lines| solve_day_1_part_1(lines),
+                        |lines| solve_day_1_part_2(lines),
+                    )
+                }
+            )*
+        }
+    }
+
+    generated_answer_tests! {
+        no_overlaps: (1, 0.0),
+        some_overlaps: (2, 0.25),
+        mostly_overlaps: (3, 0.75),
+        chained_overlaps: (4, 1.0),
+    }
+
+    #[test]
+    fn same_seed_gives_same_document() {
+        assert_seeded(|seed| CalibrationGenerator::new(seed).with_lines(50).generate())
+    }
+
+    #[test]
+    fn density_controls_overlapping_words() {
+        let extractor = NaiveExtractor::new(&DigitVocabulary::english());
+        let overlapping = |density| {
+            CalibrationGenerator::new(7)
+                .with_lines(200)
+                .with_overlap_density(density)
+                .generate()
+                .lines
+                .iter()
+                .filter(|line| has_overlapping_words(&extractor, line))
+                .count()
+        };
+
+        assert_eq!(0, overlapping(0.0));
+        assert!(overlapping(1.0) > 100)
+    }
+
+    #[test]
+    fn overlaps_include_the_puzzle_examples() {
+        let pairs: Vec<String> = overlaps()
+            .iter()
+            .map(|o| format!("{}{}", WORDS[o.a].0, &WORDS[o.b].0[o.shared..]))
+            .collect();
+
+        for example in ["eightwo", "oneight", "twone", "sevenine", "eighthree"] {
+            assert!(pairs.iter().any(|p| p == example), "{}", example)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "overlap density must be between 0 and 1")]
+    fn overlap_density_above_one_is_rejected() {
+        CalibrationGenerator::new(0).with_overlap_density(1.5);
+    }
+}
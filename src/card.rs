@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use crate::error::{offset_of, AocError};
 
 #[derive(Debug, PartialEq)]
 pub struct Card {
@@ -7,24 +9,25 @@ pub struct Card {
     pub available_numbers: Vec<u32>,
 }
 
-fn split_rest(input: &str, separator: char) -> (&str, &str) {
+fn try_split_rest(input: &str, separator: char) -> Result<(&str, &str), AocError> {
     let mut parts = input.split(separator);
-    let lhs = parts.next().expect("should have lhs");
-    let rhs = parts.next().expect("should have rhs");
+    let lhs = parts.next().unwrap_or_default();
+    let rhs = parts
+        .next()
+        .ok_or_else(|| AocError::parse(input.len() + 1, format!("expected '{}'", separator)))?;
 
-    (lhs.trim(), rhs.trim())
+    Ok((lhs.trim(), rhs.trim()))
 }
 
-fn get_numbers(input: &str) -> Vec<u32> {
-    let mut parts = input.split(' ');
-    parts
-        .into_iter()
-        .map(|c| c.parse::<u32>())
-        .map(|c| match c {
-            Ok(t) => Some(t),
-            Err(_) => None,
+fn try_get_numbers(input: &str) -> Result<Vec<u32>, AocError> {
+    input
+        .split(' ')
+        .filter(|c| !c.is_empty())
+        .map(|c| {
+            c.parse::<u32>().map_err(|_| {
+                AocError::parse(offset_of(input, c) + 1, format!("invalid number '{}'", c))
+            })
         })
-        .filter_map(|c| c.map(|c| c))
         .collect()
 }
 
@@ -39,79 +42,71 @@ pub fn win_pow(wins: Vec<u32>) -> u32 {
     }
 }
 
-fn push_many<T>(collection: &mut Vec<T>, add: &Vec<T>) -> ()
-where
-    T: Clone,
-{
-    for a in add {
-        collection.push(a.clone());
-    }
+// Cards only ever win copies of the cards directly after them, so counting
+// copies needs a window of pending counts rather than the whole table.
+#[derive(Default)]
+pub struct CardCounter {
+    pending: VecDeque<usize>,
+    total: usize,
 }
 
-fn push_wins_into_vec(cards: &HashMap<u32, Card>, wins: &mut Vec<u32>, card: &Card) -> () {
-    let current_wins = card.get_win_ids();
-    push_many(wins, &current_wins);
+impl CardCounter {
+    pub fn add(&mut self, card: &Card) {
+        let copies = 1 + self.pending.pop_front().unwrap_or(0);
+        self.total += copies;
 
-    for current_win in current_wins {
-        let card = cards.get(&current_win);
-        if let Some(c) = card {
-            push_wins_into_vec(cards, wins, c);
+        let wins = card.get_wins().len();
+        if self.pending.len() < wins {
+            self.pending.resize(wins, 0);
+        }
+        for pending in self.pending.iter_mut().take(wins) {
+            *pending += copies;
         }
     }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
 }
 
 impl Card {
-    pub fn from_string(input: &str) -> Self {
-        let (id_str, numbers) = split_rest(input, ':');
-        let id_str = id_str.replace("Card", "");
-        let id_str = id_str.trim();
-
-        let id: u32 = id_str.parse().expect("Should have id");
-
-        let (winning, all) = split_rest(numbers, '|');
-        let mut winning = get_numbers(winning);
-        let mut all = get_numbers(all);
+    pub fn try_from_string(input: &str) -> Result<Self, AocError> {
+        let (id_str, numbers) = try_split_rest(input, ':')?;
+        let id_str = id_str
+            .strip_prefix("Card")
+            .ok_or_else(|| AocError::parse(1, "expected line to start with 'Card'"))?
+            .trim();
+
+        let id: u32 = id_str
+            .parse()
+            .map_err(|_| AocError::parse(5, format!("invalid card id '{}'", id_str)))?;
+
+        let (winning, all) =
+            try_split_rest(numbers, '|').map_err(|e| e.with_offset(offset_of(input, numbers)))?;
+        let mut winning =
+            try_get_numbers(winning).map_err(|e| e.with_offset(offset_of(input, winning)))?;
+        let mut all = try_get_numbers(all).map_err(|e| e.with_offset(offset_of(input, all)))?;
 
         winning.sort();
         all.sort();
 
-        Self {
+        Ok(Self {
             id,
             winning_numbers: winning,
             available_numbers: all,
-        }
-    }
-
-    pub fn generate_from_wins(cards: HashMap<u32, Card>) -> Vec<u32> {
-        let mut win_ids: Vec<u32> = vec![];
-
-        for card in cards.iter() {
-            let (id, card) = card;
-            win_ids.push(id.clone());
-            push_wins_into_vec(&cards, &mut win_ids, card);
-        }
-
-        win_ids
+        })
     }
 
     pub fn get_wins(&self) -> Vec<u32> {
         let mut wins = vec![];
         for winning_number in self.winning_numbers.iter() {
-            if let Ok(_) = self.available_numbers.binary_search(&winning_number) {
-                wins.push(winning_number.clone());
+            if self.available_numbers.binary_search(winning_number).is_ok() {
+                wins.push(*winning_number);
             }
         }
 
         wins
     }
-
-    pub fn get_win_ids(&self) -> Vec<u32> {
-        let wins = self.get_wins();
-        let len: u32 = wins.len().try_into().expect("Should parse");
-        let id = self.id;
-
-        (1..len + 1).map(|num| id + num).collect()
-    }
 }
 
 #[cfg(test)]
@@ -146,7 +141,7 @@ mod card_tests {
                 #[test]
             fn $name() {
                     let (input, split, lhs, rhs) = $value;
-                    let (left, right) = split_rest(input, split);
+                    let (left, right) = try_split_rest(input, split).unwrap();
 
                     assert_eq!(lhs, left);
                     assert_eq!(rhs, right)
@@ -160,10 +155,29 @@ mod card_tests {
         split_with_colon: ("this or : this ", ':', "this or", "this"),
     }
 
+    #[test]
+    fn card_counter_counts_won_copies() {
+        let input = vec![
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ];
+
+        let mut counter = CardCounter::default();
+        for line in input {
+            counter.add(&Card::try_from_string(line).unwrap());
+        }
+
+        assert_eq!(30, counter.total())
+    }
+
     #[test]
     fn from_string_with_card_id_has_correct_id() {
         let input = "Card 1: 12 34 56 | 56 78";
-        let card = Card::from_string(input);
+        let card = Card::try_from_string(input).unwrap();
 
         assert_eq!(1, card.id)
     }
@@ -171,7 +185,7 @@ mod card_tests {
     #[test]
     fn from_string_with_winning_numbers_has_correct_numbers() {
         let input = "Card 1: 12 34 56 | 56 78";
-        let card = Card::from_string(input);
+        let card = Card::try_from_string(input).unwrap();
 
         let expected = vec![12, 34, 56];
 
@@ -181,7 +195,7 @@ mod card_tests {
     #[test]
     fn from_string_with_all_numbers_has_correct_numbers() {
         let input = "Card 1: 12 34 56 | 56 78";
-        let card = Card::from_string(input);
+        let card = Card::try_from_string(input).unwrap();
 
         let expected = vec![56, 78];
 
@@ -191,7 +205,7 @@ mod card_tests {
     #[test]
     fn from_string_with_no_winning_numbers_has_no_numbers() {
         let input = "Card 1: 12 34 56 | 57 78";
-        let card = Card::from_string(input);
+        let card = Card::try_from_string(input).unwrap();
 
         let expected: Vec<u32> = vec![];
         let actual = card.get_wins();
@@ -202,7 +216,7 @@ mod card_tests {
     #[test]
     fn from_string_with_one_winning_numbers_has_one_number() {
         let input = "Card 1: 12 34 56 | 56 78";
-        let card = Card::from_string(input);
+        let card = Card::try_from_string(input).unwrap();
 
         let expected: Vec<u32> = vec![56];
         let actual = card.get_wins();
@@ -213,11 +227,36 @@ mod card_tests {
     #[test]
     fn from_string_with_winning_numbers_has_numbers() {
         let input = "Card 1: 12 34 56 | 56 78 12";
-        let card = Card::from_string(input);
+        let card = Card::try_from_string(input).unwrap();
 
         let expected: Vec<u32> = vec![12, 56];
         let actual = card.get_wins();
 
         assert_eq!(expected, actual)
     }
+
+    macro_rules! try_from_string_error_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (input, expected_column) = $value;
+
+                    match Card::try_from_string(input) {
+                        Err(AocError::Parse { column, .. }) => assert_eq!(expected_column, column),
+                        other => panic!("expected parse error, got {:?}", other),
+                    }
+                }
+            )*
+        }
+    }
+
+    try_from_string_error_tests! {
+        missing_colon_is_error: ("Card 1 12 34 | 56", 18),
+        missing_card_prefix_is_error: ("Deck 1: 12 34 | 56", 1),
+        invalid_id_is_error: ("Card one: 12 34 | 56", 5),
+        missing_pipe_is_error: ("Card 1: 12 34 56", 17),
+        invalid_winning_number_is_error: ("Card 1: 12 x4 | 56", 12),
+        invalid_available_number_is_error: ("Card 1: 12 34 | 56 7!", 20),
+    }
 }
@@ -0,0 +1,217 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::AocError;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2023;
+
+const USER_AGENT: &str = "adventofcode_2023 input fetcher";
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(session: impl Into<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+
+        Self {
+            base_url: String::from(DEFAULT_BASE_URL),
+            session: session.into(),
+            agent,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    // Reads the session token from AOC_SESSION and, when set, the base URL
+    // from AOC_BASE_URL.
+    pub fn from_env() -> Result<Self, AocError> {
+        let session = env::var("AOC_SESSION")
+            .map_err(|_| AocError::Http(String::from("AOC_SESSION is not set")))?;
+        let client = Self::new(session.trim());
+
+        Ok(match env::var("AOC_BASE_URL") {
+            Ok(base_url) => client.with_base_url(base_url),
+            Err(_) => client,
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<String, AocError> {
+        let url = format!("{}/input", self.day_url(day));
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| http_error(&url, e))?;
+
+        response
+            .into_string()
+            .map_err(|e| AocError::Http(format!("{}: {}", url, e)))
+    }
+
+    pub(crate) fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<String, AocError> {
+        let response = self
+            .agent
+            .post(url)
+            .set("Cookie", &self.cookie())
+            .send_form(form)
+            .map_err(|e| http_error(url, e))?;
+
+        response
+            .into_string()
+            .map_err(|e| AocError::Http(format!("{}: {}", url, e)))
+    }
+}
+
+fn http_error(url: &str, error: ureq::Error) -> AocError {
+    match error {
+        ureq::Error::Status(code, _) => AocError::Http(format!("{} returned status {}", url, code)),
+        ureq::Error::Transport(e) => AocError::Http(format!("{}: {}", url, e)),
+    }
+}
+
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day_{}.txt", day))
+    }
+
+    pub fn contains(&self, day: u8) -> bool {
+        self.path(day).is_file()
+    }
+
+    // Returns the cached input path, downloading it first if it is missing.
+    // The second value is true when a download happened.
+    pub fn fetch(&self, client: &Client, day: u8) -> Result<(PathBuf, bool), AocError> {
+        let path = self.path(day);
+        if self.contains(day) {
+            return Ok((path, false));
+        }
+
+        let input = client.fetch_input(day)?;
+        fs::create_dir_all(&self.dir)?;
+        write_atomically(&path, &input)?;
+
+        Ok((path, true))
+    }
+}
+
+// A partially written input would be mistaken for a cached one, so write to
+// a sibling file and rename it into place.
+fn write_atomically(path: &Path, contents: &str) -> Result<(), AocError> {
+    let partial = path.with_extension("txt.partial");
+    fs::write(&partial, contents)?;
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) fn temp_dir(name: &str) -> PathBuf {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::SeqCst);
+    let dir = env::temp_dir().join(format!("aoc-{}-{}-{}", name, std::process::id(), n));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[cfg(test)]
+mod client_tests {
+    use super::*;
+    use crate::test_server::TestServer;
+
+    fn fixture(day: u8) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("inputs/day_{}.txt", day));
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn fetch_input_sends_session_cookie() {
+        let server = TestServer::start(vec![("/2023/day/2/input", 200, fixture(2))]);
+        let client = Client::new("abc123").with_base_url(&server.base_url);
+
+        let input = client.fetch_input(2).unwrap();
+
+        assert_eq!(fixture(2), input);
+        let requests = server.requests();
+        assert_eq!("/2023/day/2/input", requests[0].path);
+        assert_eq!(Some(String::from("session=abc123")), requests[0].cookie)
+    }
+
+    #[test]
+    fn fetch_input_with_error_status_is_error() {
+        let server = TestServer::start(vec![]);
+        let client = Client::new("abc123").with_base_url(&server.base_url);
+
+        let result = client.fetch_input(5);
+
+        match result {
+            Err(AocError::Http(message)) => assert!(message.ends_with("returned status 404")),
+            other => panic!("expected http error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn cache_downloads_each_input_once() {
+        let server = TestServer::start(vec![("/2023/day/1/input", 200, fixture(1))]);
+        let client = Client::new("abc123").with_base_url(format!("{}/", server.base_url));
+        let dir = temp_dir("cache");
+        let cache = InputCache::new(&dir);
+
+        let (path, fetched) = cache.fetch(&client, 1).unwrap();
+        assert!(fetched);
+        assert_eq!(dir.join("day_1.txt"), path);
+        assert_eq!(fixture(1), fs::read_to_string(&path).unwrap());
+
+        let (_, fetched) = cache.fetch(&client, 1).unwrap();
+        assert!(!fetched);
+        assert_eq!(1, server.requests().len());
+
+        fs::remove_dir_all(dir).unwrap()
+    }
+
+    #[test]
+    fn cache_does_not_keep_failed_downloads() {
+        let server = TestServer::start(vec![("/2023/day/3/input", 500, String::new())]);
+        let client = Client::new("abc123").with_base_url(&server.base_url);
+        let dir = temp_dir("failed");
+        let cache = InputCache::new(&dir);
+
+        assert!(cache.fetch(&client, 3).is_err());
+        assert!(!cache.contains(3))
+    }
+}
@@ -0,0 +1,306 @@
+use std::io::BufRead;
+use std::sync::OnceLock;
+
+use crate::answer::Answer;
+use crate::card::{win_pow, Card, CardCounter};
+use crate::engine::{self, Part};
+use crate::error::AocError;
+use crate::explain::CalibrationReport;
+use crate::game::{Bag, GameParser, Shortfall, UnknownColours};
+use crate::game_scan::{self, GameTally};
+use crate::input::Lines;
+use crate::number_phrases::{self, Combine};
+use crate::solver::Solver;
+use crate::string_utils;
+use crate::vocabulary::DigitVocabulary;
+
+const ELF_BAG: &str = "12 red, 13 green, 14 blue";
+
+pub fn calibration_sum<I, S>(lines: I, vocabulary: &DigitVocabulary) -> u64
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .map(|line| string_utils::get_number_from_string(line.as_ref(), vocabulary))
+        .sum()
+}
+
+pub fn try_calibration_sum<I, S>(lines: I, vocabulary: &DigitVocabulary) -> Result<u64, AocError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            string_utils::try_get_number_from_string(line.as_ref(), vocabulary)
+                .map_err(|e| e.at_line(i + 1))
+        })
+        .sum()
+}
+
+pub fn calibration_report<I, S>(lines: I, vocabulary: &DigitVocabulary) -> CalibrationReport
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut report = CalibrationReport::default();
+    for (i, line) in lines.into_iter().enumerate() {
+        report.add(
+            i + 1,
+            &string_utils::explain_line(line.as_ref(), vocabulary),
+        );
+    }
+    report
+}
+
+pub fn number_sum<I, S>(lines: I, rule: Combine) -> Result<u64, AocError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .enumerate()
+        .try_fold(0u64, |total, (i, line)| {
+            let value =
+                number_phrases::combine_line(line.as_ref(), rule).map_err(|e| e.at_line(i + 1))?;
+            total.checked_add(value).ok_or_else(|| {
+                AocError::parse(1, "the total of all lines does not fit in 64 bits").at_line(i + 1)
+            })
+        })
+}
+
+pub fn digits_only() -> &'static DigitVocabulary {
+    static VOCABULARY: OnceLock<DigitVocabulary> = OnceLock::new();
+    VOCABULARY.get_or_init(DigitVocabulary::digits_only)
+}
+
+pub fn english() -> &'static DigitVocabulary {
+    static VOCABULARY: OnceLock<DigitVocabulary> = OnceLock::new();
+    VOCABULARY.get_or_init(DigitVocabulary::english)
+}
+
+fn scan_games<I, S>(lines: I) -> impl Iterator<Item = Result<GameTally, AocError>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| game_scan::scan_game(line.as_ref()).map_err(|e| e.at_line(i + 1)))
+}
+
+fn elf_bag() -> Bag {
+    ELF_BAG.parse().expect("the elf's bag is well formed")
+}
+
+fn possible_game_id(game: &GameTally, bag: &Bag) -> u64 {
+    match game.is_possible_with(bag) {
+        true => game.id as u64,
+        false => 0,
+    }
+}
+
+pub fn possible_game_id_sum<I, S>(lines: I) -> Result<u64, AocError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let bag = elf_bag();
+    scan_games(lines)
+        .map(|game| game.map(|g| possible_game_id(&g, &bag)))
+        .sum()
+}
+
+// Which games a given bag could have produced. Games may use any colour;
+// one the bag does not hold counts as a shortfall.
+#[derive(Debug, Default, PartialEq)]
+pub struct BagReport {
+    pub possible: Vec<u32>,
+    pub impossible: Vec<(u32, Vec<Shortfall>)>,
+}
+
+impl BagReport {
+    pub fn id_sum(&self) -> u64 {
+        self.possible.iter().map(|id| *id as u64).sum()
+    }
+}
+
+pub fn bag_report<I, S>(lines: I, bag: &Bag) -> Result<BagReport, AocError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let parser = GameParser::default().with_unknown_colours(UnknownColours::Include);
+    let mut report = BagReport::default();
+
+    for (i, line) in lines.into_iter().enumerate() {
+        let game = parser.parse(line.as_ref()).map_err(|e| e.at_line(i + 1))?;
+        let shortfalls = game.shortfalls(bag);
+        match shortfalls.is_empty() {
+            true => report.possible.push(game.id),
+            false => report.impossible.push((game.id, shortfalls)),
+        }
+    }
+
+    Ok(report)
+}
+
+pub fn power_sum<I, S>(lines: I) -> Result<u64, AocError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    scan_games(lines).map(|game| game.map(|g| g.power())).sum()
+}
+
+pub fn part_number_sum(parts: &[Part]) -> u64 {
+    parts.iter().map(|p| p.number as u64).sum()
+}
+
+// A single ratio of two 32-bit numbers always fits in 64 bits; their total
+// may not.
+pub fn gear_ratio_sum(parts: &[Part]) -> Result<u64, AocError> {
+    engine::collect(parts)
+        .iter()
+        .filter(|(_, v)| v.len() == 2)
+        .map(|(_, v)| v[0].number as u64 * v[1].number as u64)
+        .try_fold(0u64, |total, ratio| total.checked_add(ratio))
+        .ok_or_else(|| AocError::Overflow(String::from("the total of all gear ratios")))
+}
+
+fn parse_cards<I, S>(lines: I) -> impl Iterator<Item = Result<Card, AocError>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| Card::try_from_string(line.as_ref()).map_err(|e| e.at_line(i + 1)))
+}
+
+pub fn points<I, S>(lines: I) -> Result<u32, AocError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    parse_cards(lines)
+        .map(|card| card.map(|c| win_pow(c.get_wins())))
+        .sum()
+}
+
+pub fn card_count<I, S>(lines: I) -> Result<usize, AocError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut counter = CardCounter::default();
+    for card in parse_cards(lines) {
+        counter.add(&card?);
+    }
+
+    Ok(counter.total())
+}
+
+pub struct Day1 {
+    lines: Vec<String>,
+}
+
+impl Solver for Day1 {
+    fn try_parse(input: &mut dyn BufRead) -> Result<Self, AocError> {
+        let lines = Lines::from_reader(input).process(|lines| Ok(lines.collect()))?;
+        Ok(Self { lines })
+    }
+
+    fn part1(&self) -> Answer {
+        Answer::from(calibration_sum(&self.lines, digits_only()))
+    }
+
+    fn part2(&self) -> Answer {
+        Answer::from(calibration_sum(&self.lines, english()))
+    }
+}
+
+pub struct Day2 {
+    games: Vec<GameTally>,
+}
+
+impl Solver for Day2 {
+    fn try_parse(input: &mut dyn BufRead) -> Result<Self, AocError> {
+        let games = Lines::from_reader(input).process(|lines| scan_games(lines).collect())?;
+        Ok(Self { games })
+    }
+
+    fn part1(&self) -> Answer {
+        let bag = elf_bag();
+        Answer::from(
+            self.games
+                .iter()
+                .map(|g| possible_game_id(g, &bag))
+                .sum::<u64>(),
+        )
+    }
+
+    fn part2(&self) -> Answer {
+        Answer::from(self.games.iter().map(|g| g.power()).sum::<u64>())
+    }
+}
+
+pub struct Day3 {
+    parts: Vec<Part>,
+}
+
+impl Solver for Day3 {
+    fn try_parse(input: &mut dyn BufRead) -> Result<Self, AocError> {
+        let parts = Lines::from_reader(input)
+            .rectangular()
+            .process(|lines| engine::try_process_input(lines))?;
+        Ok(Self { parts })
+    }
+
+    fn part1(&self) -> Answer {
+        Answer::from(part_number_sum(&self.parts))
+    }
+
+    fn part2(&self) -> Answer {
+        self.try_part2().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_part2(&self) -> Result<Answer, AocError> {
+        gear_ratio_sum(&self.parts).map(Answer::from)
+    }
+}
+
+pub struct Day4 {
+    cards: Vec<Card>,
+}
+
+impl Solver for Day4 {
+    fn try_parse(input: &mut dyn BufRead) -> Result<Self, AocError> {
+        let cards = Lines::from_reader(input).process(|lines| parse_cards(lines).collect())?;
+        Ok(Self { cards })
+    }
+
+    fn part1(&self) -> Answer {
+        Answer::from(
+            self.cards
+                .iter()
+                .map(|c| win_pow(c.get_wins()))
+                .sum::<u32>(),
+        )
+    }
+
+    fn part2(&self) -> Answer {
+        let mut counter = CardCounter::default();
+        for card in self.cards.iter() {
+            counter.add(card);
+        }
+        Answer::from(counter.total())
+    }
+}
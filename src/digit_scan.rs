@@ -0,0 +1,168 @@
+// Byte scanning for the digits 1-9, the calibration digits of day 1 part 1.
+// '0' never counts, matching the digit vocabulary. Nothing allocates, and on
+// x86_64 sixteen bytes are checked at a time with SSE2.
+
+fn is_calibration_digit(b: u8) -> bool {
+    (b'1'..=b'9').contains(&b)
+}
+
+pub fn first_calibration_digit_scalar(bytes: &[u8]) -> Option<usize> {
+    bytes.iter().position(|&b| is_calibration_digit(b))
+}
+
+pub fn last_calibration_digit_scalar(bytes: &[u8]) -> Option<usize> {
+    bytes.iter().rposition(|&b| is_calibration_digit(b))
+}
+
+#[cfg(target_arch = "x86_64")]
+mod sse2 {
+    use std::arch::x86_64::{
+        __m128i, _mm_and_si128, _mm_cmpgt_epi8, _mm_cmplt_epi8, _mm_loadu_si128, _mm_movemask_epi8,
+        _mm_set1_epi8,
+    };
+
+    use super::{first_calibration_digit_scalar, last_calibration_digit_scalar};
+
+    const LANES: usize = 16;
+
+    // One bit per byte of `chunk`, set where the byte is '1' to '9'. The
+    // comparisons are signed, so bytes from 0x80 up fail the lower bound.
+    fn digit_mask(chunk: &[u8]) -> u32 {
+        assert_eq!(LANES, chunk.len());
+        // SAFETY: SSE2 is part of the x86_64 baseline, the load is unaligned
+        // and the assert above keeps it inside `chunk`.
+        unsafe {
+            let bytes = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
+            let above = _mm_cmpgt_epi8(bytes, _mm_set1_epi8(b'0' as i8));
+            let below = _mm_cmplt_epi8(bytes, _mm_set1_epi8(b':' as i8));
+            _mm_movemask_epi8(_mm_and_si128(above, below)) as u32
+        }
+    }
+
+    pub fn first(bytes: &[u8]) -> Option<usize> {
+        let chunks = bytes.chunks_exact(LANES);
+        let tail = chunks.remainder();
+
+        for (i, chunk) in chunks.enumerate() {
+            let mask = digit_mask(chunk);
+            if mask != 0 {
+                return Some(i * LANES + mask.trailing_zeros() as usize);
+            }
+        }
+
+        let offset = bytes.len() - tail.len();
+        first_calibration_digit_scalar(tail).map(|i| offset + i)
+    }
+
+    pub fn last(bytes: &[u8]) -> Option<usize> {
+        let chunks = bytes.rchunks_exact(LANES);
+        let head = chunks.remainder();
+
+        for (i, chunk) in chunks.enumerate() {
+            let mask = digit_mask(chunk);
+            if mask != 0 {
+                let offset = bytes.len() - (i + 1) * LANES;
+                return Some(offset + 31 - mask.leading_zeros() as usize);
+            }
+        }
+
+        last_calibration_digit_scalar(head)
+    }
+}
+
+pub fn first_calibration_digit(bytes: &[u8]) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    return sse2::first(bytes);
+
+    #[cfg(not(target_arch = "x86_64"))]
+    return first_calibration_digit_scalar(bytes);
+}
+
+pub fn last_calibration_digit(bytes: &[u8]) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    return sse2::last(bytes);
+
+    #[cfg(not(target_arch = "x86_64"))]
+    return last_calibration_digit_scalar(bytes);
+}
+
+// The part 1 answer for a whole document, read straight from its bytes.
+// Lines may end in "\n" or "\r\n"; a line without a digit is worth 0.
+pub fn calibration_digit_sum(input: &[u8]) -> u64 {
+    input
+        .split(|&b| b == b'\n')
+        .filter_map(|line| {
+            let first = first_calibration_digit(line)?;
+            let last = last_calibration_digit(line)?;
+            Some(((line[first] - b'0') * 10 + (line[last] - b'0')) as u64)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod digit_scan_tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    macro_rules! digit_scan_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (input, expected): (&str, Option<(usize, usize)>) = $value;
+                    let bytes = input.as_bytes();
+                    let found = first_calibration_digit(bytes).zip(last_calibration_digit(bytes));
+                    assert_eq!(expected, found)
+                }
+            )*
+        }
+    }
+
+    digit_scan_tests! {
+        short_line: ("a1b2c", Some((1, 3))),
+        single_digit: ("treb7uchet", Some((4, 4))),
+        zero_is_skipped: ("0a0", None),
+        empty: ("", None),
+        digit_in_second_chunk: ("abcdefghijklmnopq5rs", Some((17, 17))),
+        digits_at_both_ends_of_long_line: ("1bcdefghijklmnopqrstuvwxyzabcdef9", Some((0, 32))),
+        digit_in_unaligned_head: ("7abcdefghijklmnopqrstuvwxyzabcdefg", Some((0, 0))),
+        multibyte_text_is_not_a_digit: ("\u{ff11}é\u{663}ü", None),
+    }
+
+    #[test]
+    fn sse2_agrees_with_scalar_on_random_bytes() {
+        let mut rng = Rng::new(19);
+        let alphabet = b"abc0123456789\xc3\xa9\xff/:";
+
+        for _ in 0..2000 {
+            let len = rng.below(80);
+            // Digits are made rare so that long digit-free runs cross chunks.
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| match rng.below(8) {
+                    0 => *rng.choose(alphabet),
+                    _ => *rng.choose(b"xyz/:\x80"),
+                })
+                .collect();
+
+            assert_eq!(
+                first_calibration_digit_scalar(&bytes),
+                first_calibration_digit(&bytes),
+                "{:?}",
+                bytes
+            );
+            assert_eq!(
+                last_calibration_digit_scalar(&bytes),
+                last_calibration_digit(&bytes),
+                "{:?}",
+                bytes
+            )
+        }
+    }
+
+    #[test]
+    fn document_sum_matches_example() {
+        let input = b"1abc2\r\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\nnone\n";
+
+        assert_eq!(142, calibration_digit_sum(input))
+    }
+}
@@ -0,0 +1,61 @@
+// A generated puzzle input together with the answers it was built to have,
+// worked out while generating rather than by any solver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document {
+    pub lines: Vec<String>,
+    pub part_1: u64,
+    pub part_2: u64,
+}
+
+impl Document {
+    pub(crate) fn with_capacity(lines: usize) -> Self {
+        Self {
+            lines: Vec::with_capacity(lines),
+            part_1: 0,
+            part_2: 0,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        text
+    }
+}
+
+// Checks shared by every generator's tests.
+#[cfg(test)]
+pub(crate) mod document_checks {
+    use super::Document;
+    use crate::answer::Answer;
+
+    pub fn assert_answers(
+        document: &Document,
+        part_1: impl Fn(&[String]) -> Answer,
+        part_2: impl Fn(&[String]) -> Answer,
+    ) {
+        assert_eq!(Answer::from(document.part_1), part_1(&document.lines));
+        assert_eq!(Answer::from(document.part_2), part_2(&document.lines))
+    }
+
+    pub fn assert_seeded(generate: impl Fn(u64) -> Document) {
+        assert_eq!(generate(42), generate(42));
+        assert_ne!(generate(42), generate(43))
+    }
+}
+
+#[cfg(test)]
+mod document_tests {
+    use super::*;
+
+    #[test]
+    fn text_ends_every_line_with_a_newline() {
+        let document = Document {
+            lines: vec![String::from("a"), String::from("b")],
+            part_1: 0,
+            part_2: 0,
+        };
+
+        assert_eq!("a\nb\n", document.text())
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use crate::engine::Component::Gear;
+use crate::error::AocError;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Component {
@@ -16,12 +17,12 @@ pub struct Part {
 
 pub fn get_component_index(component: Component) -> usize {
     match component {
-        Gear(s) => s.clone(),
-        Component::Component(_, s) => s.clone(),
+        Gear(s) => s,
+        Component::Component(_, s) => s,
     }
 }
 
-pub fn collect(parts: Vec<Part>) -> HashMap<usize, Vec<Part>> {
+pub fn collect(parts: &[Part]) -> HashMap<usize, Vec<&Part>> {
     let mut hashmap = HashMap::new();
 
     for part in parts {
@@ -32,63 +33,72 @@ pub fn collect(parts: Vec<Part>) -> HashMap<usize, Vec<Part>> {
     hashmap
 }
 
-pub fn process_input(input: Vec<String>) -> Vec<Part> {
+pub fn try_process_input<I, S>(input: I) -> Result<Vec<Part>, AocError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
     let mut parts = vec![];
+    let mut lines = input
+        .into_iter()
+        .map(|line| line.as_ref().chars().collect::<Vec<char>>());
+
+    let mut chars_above: Vec<char> = vec![];
+    let mut current = lines.next();
+    let mut chars_below = lines.next();
+    let mut i = 0;
+
+    while let Some(chars) = current {
+        let below = chars_below.as_deref().unwrap_or_default();
+        process_line(&chars_above, &chars, below, i, &mut parts)?;
+
+        chars_above = chars;
+        current = chars_below;
+        chars_below = lines.next();
+        i += 1;
+    }
 
-    for i in 0..input.len() {
-        let line = input.get(i).expect("Invalid: Should have line here");
-        let chars: Vec<char> = line.chars().collect();
-
-        let chars_above = if i == 0 {
-            vec![]
-        } else {
-            input.get(i - 1).map_or(vec![], |s| s.chars().collect())
-        };
-
-        let chars_below = input.get(i + 1).map_or(vec![], |s| s.chars().collect());
-
-        let mut num = 0u32;
+    Ok(parts)
+}
 
-        let mut start_index = 0;
-        for j in 0..line.len() {
-            let current_char = chars.get(j).expect("Should always get a character here");
-            if current_char.is_numeric() {
-                let digit: u32 = current_char.to_digit(10).expect("Should be numerical");
-                if num == 0 {
-                    start_index = j;
-                }
-                num = (num * 10) + digit;
-            } else {
-                if num > 0 {
-                    let end_index = if j == 0 { 0 } else { j - 1 };
-                    let components = check_surroundings(
-                        chars_above.clone(),
-                        chars_below.clone(),
-                        chars.clone(),
-                        i,
-                        line.len(),
-                        start_index,
-                        end_index,
-                    );
-                    for component in components {
-                        parts.push(Part {
-                            number: num,
-                            component,
-                        })
-                    }
-                    num = 0;
-                }
+fn process_line(
+    chars_above: &[char],
+    chars: &[char],
+    chars_below: &[char],
+    i: usize,
+    parts: &mut Vec<Part>,
+) -> Result<(), AocError> {
+    let len = chars.len();
+    let mut num = 0u32;
+
+    let mut start_index = 0;
+    for (j, current_char) in chars.iter().enumerate() {
+        if current_char.is_numeric() {
+            let digit: u32 = current_char.to_digit(10).ok_or_else(|| {
+                AocError::parse(
+                    j + 1,
+                    format!("unsupported numeric character '{}'", current_char),
+                )
+                .at_line(i + 1)
+            })?;
+            if num == 0 {
+                start_index = j;
             }
-        }
-        if num > 0 {
-            let j = line.len() - 1;
+            num = num
+                .checked_mul(10)
+                .and_then(|n| n.checked_add(digit))
+                .ok_or_else(|| {
+                    AocError::parse(start_index + 1, "part number does not fit in 32 bits")
+                        .at_line(i + 1)
+                })?;
+        } else if num > 0 {
             let end_index = if j == 0 { 0 } else { j - 1 };
             let components = check_surroundings(
-                chars_above.clone(),
-                chars_below.clone(),
-                chars.clone(),
+                chars_above,
+                chars_below,
+                chars,
                 i,
-                line.len(),
+                len,
                 start_index,
                 end_index,
             );
@@ -98,17 +108,36 @@ pub fn process_input(input: Vec<String>) -> Vec<Part> {
                     component,
                 })
             }
+            num = 0;
+        }
+    }
+    if num > 0 {
+        let j = len - 1;
+        let end_index = if j == 0 { 0 } else { j - 1 };
+        let components = check_surroundings(
+            chars_above,
+            chars_below,
+            chars,
+            i,
+            len,
+            start_index,
+            end_index,
+        );
+        for component in components {
+            parts.push(Part {
+                number: num,
+                component,
+            })
         }
-        num = 0;
     }
 
-    parts
+    Ok(())
 }
 
 fn check_surroundings(
-    above: Vec<char>,
-    below: Vec<char>,
-    current: Vec<char>,
+    above: &[char],
+    below: &[char],
+    current: &[char],
     row_index: usize,
     len: usize,
     start_index: usize,
@@ -156,7 +185,7 @@ fn try_create_component(
             let component_index = calculate_component_index(row_index, len, jpos);
             match is_symbol(c) {
                 false => None,
-                true => Some(get_component(c.clone(), component_index)),
+                true => Some(get_component(*c, component_index)),
             }
         }
     }
@@ -169,15 +198,13 @@ fn calculate_component_index(row_index: usize, len: usize, jpos: usize) -> usize
 fn get_component(input: char, component_index: usize) -> Component {
     match input {
         '*' => Gear(component_index),
-        _ => Component::Component(input.clone(), component_index),
+        _ => Component::Component(input, component_index),
     }
 }
 
 fn is_symbol(input: &char) -> bool {
     match input {
         '.' => false,
-        '\r' => false,
-        '\n' => false,
         x if x.is_numeric() => false,
         _ => true,
     }
@@ -186,6 +213,7 @@ fn is_symbol(input: &char) -> bool {
 #[cfg(test)]
 mod engine_tests {
     use super::*;
+    use crate::input::Lines;
 
     macro_rules! is_symbol_tests {
         ($($name:ident: $value:expr,)*) => {
@@ -210,7 +238,7 @@ mod engine_tests {
     #[test]
     fn collect_parts_single_line_no_parts_returns_empty_collection() {
         let input = "........";
-        let actual = process_input(vec![String::from(input)]);
+        let actual = try_process_input(vec![String::from(input)]).unwrap();
         let expected: Vec<Part> = vec![];
 
         assert_eq!(expected, actual)
@@ -219,7 +247,7 @@ mod engine_tests {
     #[test]
     fn collect_parts_single_line_with_number_no_parts_returns_empty_collection() {
         let input = "123.....";
-        let actual = process_input(vec![String::from(input)]);
+        let actual = try_process_input(vec![String::from(input)]).unwrap();
         let expected: Vec<Part> = vec![];
 
         assert_eq!(expected, actual)
@@ -228,7 +256,7 @@ mod engine_tests {
     #[test]
     fn collect_parts_single_line_with_number_and_symbol_returns_single_item() {
         let input = "123*.....";
-        let actual = process_input(vec![String::from(input)]);
+        let actual = try_process_input(vec![String::from(input)]).unwrap();
         let expected = vec![Part {
             number: 123,
             component: Gear(3),
@@ -240,7 +268,7 @@ mod engine_tests {
     #[test]
     fn collect_parts_single_line_with_symbol_then_number_returns_single_item() {
         let input = "*123......";
-        let actual = process_input(vec![String::from(input)]);
+        let actual = try_process_input(vec![String::from(input)]).unwrap();
         let expected = vec![Part {
             number: 123,
             component: Gear(0),
@@ -252,7 +280,7 @@ mod engine_tests {
     #[test]
     fn collect_parts_single_line_multiple_parts_returns_items() {
         let input = "*123..234#";
-        let actual = process_input(vec![String::from(input)]);
+        let actual = try_process_input(vec![String::from(input)]).unwrap();
 
         let expected = vec![
             Part {
@@ -271,7 +299,7 @@ mod engine_tests {
     #[test]
     fn collect_parts_single_line_with_symbol_no_nuber_returns_no_items() {
         let input = "...*....";
-        let actual = process_input(vec![String::from(input)]);
+        let actual = try_process_input(vec![String::from(input)]).unwrap();
 
         let expected: Vec<Part> = vec![];
 
@@ -286,7 +314,7 @@ mod engine_tests {
             String::from("........"),
         ];
 
-        let actual = process_input(input);
+        let actual = try_process_input(input).unwrap();
 
         let expected = vec![Part {
             number: 123,
@@ -304,7 +332,7 @@ mod engine_tests {
             String::from("........"),
         ];
 
-        let actual = process_input(input);
+        let actual = try_process_input(input).unwrap();
 
         let expected = vec![Part {
             number: 123,
@@ -322,7 +350,7 @@ mod engine_tests {
             String::from(".......#"),
         ];
 
-        let actual = process_input(input);
+        let actual = try_process_input(input).unwrap();
 
         let expected = vec![Part {
             number: 123,
@@ -340,7 +368,7 @@ mod engine_tests {
             String::from("........"),
         ];
 
-        let actual = process_input(input);
+        let actual = try_process_input(input).unwrap();
 
         let expected = vec![Part {
             number: 123,
@@ -359,7 +387,9 @@ mod engine_tests {
             String::from("......78\r\n"),
         ];
 
-        let actual = process_input(input);
+        let actual = Lines::from_lines(input)
+            .process(|lines| try_process_input(lines))
+            .unwrap();
 
         let expected = vec![
             Part {
@@ -368,19 +398,19 @@ mod engine_tests {
             },
             Part {
                 number: 123,
-                component: Gear(15),
+                component: Gear(13),
             },
             Part {
                 number: 123,
-                component: Component::Component('#', 23),
+                component: Component::Component('#', 19),
             },
             Part {
                 number: 456,
-                component: Component::Component('#', 23),
+                component: Component::Component('#', 19),
             },
             Part {
                 number: 456,
-                component: Gear(15),
+                component: Gear(13),
             },
         ];
 
@@ -395,7 +425,7 @@ mod engine_tests {
             String::from("........"),
         ];
 
-        let actual = process_input(input);
+        let actual = try_process_input(input).unwrap();
 
         let expected: Vec<Part> = vec![];
 
@@ -410,7 +440,7 @@ mod engine_tests {
             String::from("........"),
         ];
 
-        let actual = process_input(input);
+        let actual = try_process_input(input).unwrap();
 
         let expected = vec![Part {
             number: 123,
@@ -428,7 +458,7 @@ mod engine_tests {
             String::from("......*."),
         ];
 
-        let actual = process_input(input);
+        let actual = try_process_input(input).unwrap();
 
         let expected = vec![
             Part {
@@ -447,4 +477,27 @@ mod engine_tests {
 
         assert_eq!(expected, actual)
     }
+
+    #[test]
+    fn try_process_input_with_non_decimal_numeric_is_error() {
+        let input = vec![String::from("......"), String::from("..1½*.")];
+
+        match try_process_input(input) {
+            Err(AocError::Parse { line, column, .. }) => {
+                assert_eq!(2, line);
+                assert_eq!(4, column)
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn try_process_input_with_oversized_number_is_error() {
+        let input = vec![String::from("......"), String::from(".*12345678901.")];
+
+        match try_process_input(input) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((2, 3), (line, column)),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
 }
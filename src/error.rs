@@ -0,0 +1,135 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum AocError {
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    NoDigit {
+        line: usize,
+    },
+    Io(io::Error),
+    Manifest(String),
+    History(String),
+    Http(String),
+    Attempts(String),
+    Vocabulary(String),
+    Inference(String),
+    Overflow(String),
+}
+
+impl AocError {
+    pub fn parse(column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line: 1,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        match self {
+            AocError::Parse {
+                column, message, ..
+            } => AocError::Parse {
+                line,
+                column,
+                message,
+            },
+            AocError::NoDigit { .. } => AocError::NoDigit { line },
+            other => other,
+        }
+    }
+
+    pub fn with_offset(self, offset: usize) -> Self {
+        match self {
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => AocError::Parse {
+                line,
+                column: column + offset,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            AocError::NoDigit { line } => write!(f, "line {}: no digit found", line),
+            AocError::Io(e) => write!(f, "io error: {}", e),
+            AocError::Manifest(message) => write!(f, "invalid answers manifest: {}", message),
+            AocError::History(message) => write!(f, "invalid benchmark history: {}", message),
+            AocError::Http(message) => write!(f, "http error: {}", message),
+            AocError::Attempts(message) => write!(f, "invalid submission log: {}", message),
+            AocError::Vocabulary(message) => write!(f, "invalid number word table: {}", message),
+            AocError::Inference(message) => write!(f, "cannot infer the bag for {}", message),
+            AocError::Overflow(message) => write!(f, "{} does not fit in 64 bits", message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+pub fn offset_of(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+#[cfg(test)]
+mod error_tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_displays_line_and_column() {
+        let error = AocError::parse(7, "expected ':'").at_line(3);
+
+        assert_eq!("line 3, column 7: expected ':'", error.to_string())
+    }
+
+    #[test]
+    fn no_digit_error_takes_line() {
+        let error = AocError::NoDigit { line: 1 }.at_line(9);
+
+        assert_eq!("line 9: no digit found", error.to_string())
+    }
+
+    #[test]
+    fn with_offset_shifts_column() {
+        let error = AocError::parse(2, "bad").with_offset(10);
+
+        assert!(matches!(error, AocError::Parse { column: 12, .. }))
+    }
+
+    #[test]
+    fn offset_of_substring_is_byte_distance() {
+        let outer = "Game 1: 3 blue";
+        let inner = &outer[8..];
+
+        assert_eq!(8, offset_of(outer, inner))
+    }
+}
@@ -0,0 +1,203 @@
+use crate::vocabulary::DigitMatch;
+
+const ANSI_FIRST: &str = "\x1b[32m";
+const ANSI_LAST: &str = "\x1b[33m";
+const ANSI_BOTH: &str = "\x1b[35m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Highlight {
+    Ansi,
+    Brackets,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub line: String,
+    pub first: Option<DigitMatch>,
+    pub last: Option<DigitMatch>,
+    pub value: u64,
+}
+
+impl Explanation {
+    pub fn new(line: &str, first: Option<DigitMatch>, last: Option<DigitMatch>) -> Self {
+        let value = match (first, last) {
+            (Some(first), Some(last)) => (first.value * 10 + last.value) as u64,
+            _ => 0,
+        };
+
+        Self {
+            line: line.to_string(),
+            first,
+            last,
+            value,
+        }
+    }
+
+    pub fn first_text(&self) -> Option<&str> {
+        self.first.map(|m| m.text(&self.line))
+    }
+
+    pub fn last_text(&self) -> Option<&str> {
+        self.last.map(|m| m.text(&self.line))
+    }
+
+    pub fn render(&self, highlight: Highlight) -> String {
+        match highlight {
+            Highlight::Ansi => self.render_ansi(),
+            Highlight::Brackets => self.render_brackets(),
+        }
+    }
+
+    // The first match is green and the last yellow; bytes shared by both,
+    // as in the 't' of "eightwo", are magenta.
+    fn render_ansi(&self) -> String {
+        let covers = |m: Option<DigitMatch>, i: usize| m.is_some_and(|m| m.start <= i && i < m.end);
+
+        let mut boundaries: Vec<usize> = [self.first, self.last]
+            .into_iter()
+            .flatten()
+            .flat_map(|m| [m.start, m.end])
+            .chain([0, self.line.len()])
+            .collect();
+        boundaries.sort();
+        boundaries.dedup();
+
+        let mut rendered = String::new();
+        for window in boundaries.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            let segment = &self.line[start..end];
+            let colour = match (covers(self.first, start), covers(self.last, start)) {
+                (true, true) => Some(ANSI_BOTH),
+                (true, false) => Some(ANSI_FIRST),
+                (false, true) => Some(ANSI_LAST),
+                (false, false) => None,
+            };
+
+            match colour {
+                Some(colour) => {
+                    rendered.push_str(colour);
+                    rendered.push_str(segment);
+                    rendered.push_str(ANSI_RESET);
+                }
+                None => rendered.push_str(segment),
+            }
+        }
+
+        rendered
+    }
+
+    // The first match is wrapped in [] and the last in {}, so overlapping
+    // matches interleave: "[eigh{t]wo}".
+    fn render_brackets(&self) -> String {
+        let mut markers = vec![];
+        if let Some(first) = self.first {
+            markers.push((first.start, 1, '['));
+            markers.push((first.end, 0, ']'));
+        }
+        if let Some(last) = self.last {
+            markers.push((last.start, 2, '{'));
+            markers.push((last.end, 0, '}'));
+        }
+        // Closing markers sort before opening ones at the same offset, and
+        // '}' before ']' so a shared match renders as "[{7}]".
+        markers.sort_by_key(|&(offset, order, marker)| (offset, order, marker == ']'));
+
+        let mut rendered = String::new();
+        let mut position = 0;
+        for (offset, _, marker) in markers {
+            rendered.push_str(&self.line[position..offset]);
+            rendered.push(marker);
+            position = offset;
+        }
+        rendered.push_str(&self.line[position..]);
+
+        rendered
+    }
+}
+
+// Totals calibration values while remembering which lines had no digit at
+// all, so corrupted input can be reported instead of silently adding 0.
+#[derive(Debug, Default, PartialEq)]
+pub struct CalibrationReport {
+    pub total: u64,
+    pub missing: Vec<usize>,
+}
+
+impl CalibrationReport {
+    pub fn add(&mut self, line: usize, explanation: &Explanation) {
+        self.total += explanation.value;
+        if explanation.first.is_none() {
+            self.missing.push(line);
+        }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod explain_tests {
+    use super::*;
+    use crate::string_utils::explain_line;
+    use crate::vocabulary::DigitVocabulary;
+
+    fn explain(line: &str) -> Explanation {
+        explain_line(line, &DigitVocabulary::english())
+    }
+
+    macro_rules! render_brackets_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (line, expected) = $value;
+                    assert_eq!(expected, explain(line).render(Highlight::Brackets))
+                }
+            )*
+        }
+    }
+
+    render_brackets_tests! {
+        separate_matches: ("two1nine", "[two]1{nine}"),
+        overlapping_matches: ("eightwo", "[eigh{t]wo}"),
+        adjacent_matches: ("1two", "[1]{two}"),
+        single_match_is_both: ("treb7uchet", "treb[{7}]uchet"),
+        no_match_is_unchanged: ("abc", "abc"),
+        multibyte_text_around_matches: ("é9ü", "é[{9}]ü"),
+    }
+
+    #[test]
+    fn explanation_reports_spans_text_and_value() {
+        let explanation = explain("xtwone3four");
+
+        assert_eq!(Some("two"), explanation.first_text());
+        assert_eq!(Some("four"), explanation.last_text());
+        assert_eq!(Some((1, 4)), explanation.first.map(|m| (m.start, m.end)));
+        assert_eq!(Some((7, 11)), explanation.last.map(|m| (m.start, m.end)));
+        assert_eq!(24, explanation.value)
+    }
+
+    #[test]
+    fn report_lists_lines_without_digits() {
+        let mut report = CalibrationReport::default();
+        for (i, line) in ["two1nine", "", "xyz", "7"].iter().enumerate() {
+            report.add(i + 1, &explain(line));
+        }
+
+        assert_eq!(29 + 77, report.total);
+        assert_eq!(vec![2, 3], report.missing);
+        assert!(!report.is_clean())
+    }
+
+    #[test]
+    fn render_ansi_colours_first_last_and_shared_bytes() {
+        let rendered = explain("eightwo").render(Highlight::Ansi);
+
+        assert_eq!(
+            "\x1b[32meigh\x1b[0m\x1b[35mt\x1b[0m\x1b[33mwo\x1b[0m",
+            rendered
+        )
+    }
+}
@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::vocabulary::{DigitMatch, DigitVocabulary};
+
+// Finds the first and last digit of a calibration line. Every strategy
+// agrees on what a match is: among the vocabulary's patterns, the first
+// match has the leftmost start and the last the rightmost, and at a given
+// start the longest pattern wins, with ties going to table order.
+pub trait DigitExtractor {
+    fn first_match(&self, line: &str) -> Option<DigitMatch>;
+    fn last_match(&self, line: &str) -> Option<DigitMatch>;
+
+    fn calibration_value(&self, line: &str) -> u64 {
+        match (self.first_match(line), self.last_match(line)) {
+            (Some(first), Some(last)) => (first.value * 10 + last.value) as u64,
+            _ => 0,
+        }
+    }
+}
+
+// The automaton strategy: one pass forward for the first match and one pass
+// backward for the last.
+impl DigitExtractor for DigitVocabulary {
+    fn first_match(&self, line: &str) -> Option<DigitMatch> {
+        DigitVocabulary::first_match(self, line)
+    }
+
+    fn last_match(&self, line: &str) -> Option<DigitMatch> {
+        DigitVocabulary::last_match(self, line)
+    }
+}
+
+fn alternative(pattern: &str, case_insensitive: bool) -> String {
+    pattern
+        .chars()
+        .map(|c| match case_insensitive && c.is_ascii_alphabetic() {
+            true => format!("[{}{}]", c.to_ascii_lowercase(), c.to_ascii_uppercase()),
+            false => regex::escape(&c.to_string()),
+        })
+        .collect()
+}
+
+// The regex crate takes the first alternative that matches at the leftmost
+// start, so alternatives are ordered longest first. The last match is found
+// by letting a greedy prefix swallow as much of the line as it can.
+pub struct RegexExtractor {
+    first: Regex,
+    last: Regex,
+    values: HashMap<String, u32>,
+    case_insensitive: bool,
+}
+
+impl RegexExtractor {
+    pub fn new(vocabulary: &DigitVocabulary) -> Self {
+        let case_insensitive = vocabulary.is_case_insensitive();
+        let mut table = vocabulary.pattern_table();
+        table.sort_by_key(|(pattern, _)| std::cmp::Reverse(pattern.len()));
+
+        let mut values = HashMap::new();
+        for (pattern, value) in table.iter() {
+            let key = match case_insensitive {
+                true => pattern.to_ascii_lowercase(),
+                false => pattern.clone(),
+            };
+            values.entry(key).or_insert(*value);
+        }
+
+        let alternatives: Vec<String> = table
+            .iter()
+            .map(|(pattern, _)| alternative(pattern, case_insensitive))
+            .collect();
+        let alternatives = alternatives.join("|");
+
+        Self {
+            first: Regex::new(&alternatives).expect("escaped patterns are valid"),
+            last: Regex::new(&format!("(?s).*({})", alternatives))
+                .expect("escaped patterns are valid"),
+            values,
+            case_insensitive,
+        }
+    }
+
+    fn to_digit_match(&self, line: &str, start: usize, end: usize) -> DigitMatch {
+        let text = &line[start..end];
+        let value = match self.case_insensitive {
+            true => self.values[&text.to_ascii_lowercase()],
+            false => self.values[text],
+        };
+
+        DigitMatch { start, end, value }
+    }
+}
+
+impl DigitExtractor for RegexExtractor {
+    fn first_match(&self, line: &str) -> Option<DigitMatch> {
+        let found = self.first.find(line)?;
+        Some(self.to_digit_match(line, found.start(), found.end()))
+    }
+
+    fn last_match(&self, line: &str) -> Option<DigitMatch> {
+        let found = self.last.captures(line)?.get(1)?;
+        Some(self.to_digit_match(line, found.start(), found.end()))
+    }
+}
+
+// The longest pattern starting exactly at `start`, if any.
+fn longest_at(
+    table: &[(String, u32)],
+    case_insensitive: bool,
+    line: &str,
+    start: usize,
+) -> Option<DigitMatch> {
+    let rest = &line.as_bytes()[start..];
+    let mut best: Option<DigitMatch> = None;
+
+    for (pattern, value) in table {
+        let pattern = pattern.as_bytes();
+        let Some(prefix) = rest.get(..pattern.len()) else {
+            continue;
+        };
+        let matched = match case_insensitive {
+            true => prefix.eq_ignore_ascii_case(pattern),
+            false => prefix == pattern,
+        };
+
+        if matched && best.is_none_or(|m| pattern.len() > m.end - m.start) {
+            best = Some(DigitMatch {
+                start,
+                end: start + pattern.len(),
+                value: *value,
+            });
+        }
+    }
+
+    best
+}
+
+// Tries every pattern at every char boundary and keeps whatever matched.
+// Slow, but simple enough to serve as the reference the others are held to.
+pub struct NaiveExtractor {
+    table: Vec<(String, u32)>,
+    case_insensitive: bool,
+}
+
+impl NaiveExtractor {
+    pub fn new(vocabulary: &DigitVocabulary) -> Self {
+        Self {
+            table: vocabulary.pattern_table(),
+            case_insensitive: vocabulary.is_case_insensitive(),
+        }
+    }
+
+    pub fn matches(&self, line: &str) -> Vec<DigitMatch> {
+        line.char_indices()
+            .filter_map(|(start, _)| longest_at(&self.table, self.case_insensitive, line, start))
+            .collect()
+    }
+}
+
+impl DigitExtractor for NaiveExtractor {
+    fn first_match(&self, line: &str) -> Option<DigitMatch> {
+        self.matches(line).first().copied()
+    }
+
+    fn last_match(&self, line: &str) -> Option<DigitMatch> {
+        self.matches(line).last().copied()
+    }
+}
+
+// Walks char boundaries from the front for the first match and from the back
+// for the last, stopping at the first hit in each direction.
+pub struct ReverseScanExtractor {
+    table: Vec<(String, u32)>,
+    case_insensitive: bool,
+}
+
+impl ReverseScanExtractor {
+    pub fn new(vocabulary: &DigitVocabulary) -> Self {
+        Self {
+            table: vocabulary.pattern_table(),
+            case_insensitive: vocabulary.is_case_insensitive(),
+        }
+    }
+}
+
+impl DigitExtractor for ReverseScanExtractor {
+    fn first_match(&self, line: &str) -> Option<DigitMatch> {
+        line.char_indices()
+            .find_map(|(start, _)| longest_at(&self.table, self.case_insensitive, line, start))
+    }
+
+    fn last_match(&self, line: &str) -> Option<DigitMatch> {
+        line.char_indices()
+            .rev()
+            .find_map(|(start, _)| longest_at(&self.table, self.case_insensitive, line, start))
+    }
+}
+
+#[cfg(test)]
+mod extractor_tests {
+    use super::*;
+    use crate::rng::Rng;
+    use crate::string_utils::DigitSet;
+
+    const FRAGMENTS: [&str; 24] = [
+        "one", "two", "thr", "ee", "eight", "nin", "e", "seven", "on", "ei", "ght", "zwei", "fünf",
+        "FÜNF", "ONE", "deux", "dos", "siete", "ab", "c", "x", "q", "é", "ü",
+    ];
+    const SINGLES: [&str; 8] = ["1", "7", "0", "\u{ff17}", "\u{663}", " ", "-", "\u{1f384}"];
+
+    const ADVERSARIAL: [&str; 16] = [
+        "eightwothree",
+        "eightoneight",
+        "oneight",
+        "sevenine",
+        "twone",
+        "xtwone3four",
+        "zoneight234",
+        "nineight",
+        "eigh",
+        "tw",
+        "seve",
+        "ninine",
+        "",
+        "abcbc",
+        "fünfünf",
+        "EightWoThree",
+    ];
+
+    fn vocabularies() -> Vec<(&'static str, DigitVocabulary)> {
+        vec![
+            ("digits only", DigitVocabulary::digits_only()),
+            ("english", DigitVocabulary::english()),
+            (
+                "english case insensitive",
+                DigitVocabulary::english().case_insensitive(),
+            ),
+            (
+                "german case insensitive",
+                DigitVocabulary::german().case_insensitive(),
+            ),
+            ("french", DigitVocabulary::french()),
+            (
+                "spanish unicode",
+                DigitVocabulary::spanish().with_digits(DigitSet::Unicode),
+            ),
+            (
+                "nested",
+                DigitVocabulary::from_table(&[("ab", 1), ("abc", 2), ("b", 3), ("bc", 4)]).unwrap(),
+            ),
+        ]
+    }
+
+    fn extractors(vocabulary: &DigitVocabulary) -> Vec<(&'static str, Box<dyn DigitExtractor>)> {
+        vec![
+            ("regex", Box::new(RegexExtractor::new(vocabulary))),
+            ("naive", Box::new(NaiveExtractor::new(vocabulary))),
+            (
+                "reverse scan",
+                Box::new(ReverseScanExtractor::new(vocabulary)),
+            ),
+        ]
+    }
+
+    // Holds every strategy to the automaton's answer on each line.
+    fn assert_agree<'a>(
+        name: &str,
+        vocabulary: &DigitVocabulary,
+        lines: impl Iterator<Item = &'a str>,
+    ) {
+        let extractors = extractors(vocabulary);
+
+        for line in lines {
+            let expected = (vocabulary.first_match(line), vocabulary.last_match(line));
+            for (strategy, extractor) in extractors.iter() {
+                assert_eq!(
+                    expected,
+                    (extractor.first_match(line), extractor.last_match(line)),
+                    "{} disagrees with the automaton on {:?} using {}",
+                    strategy,
+                    line,
+                    name
+                )
+            }
+        }
+    }
+
+    fn random_line(rng: &mut Rng) -> String {
+        (0..rng.below(12))
+            .map(|_| match rng.below(3) {
+                0 => *rng.choose(&SINGLES),
+                _ => *rng.choose(&FRAGMENTS),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn strategies_agree_on_adversarial_lines() {
+        for (name, vocabulary) in vocabularies() {
+            assert_agree(name, &vocabulary, ADVERSARIAL.into_iter())
+        }
+    }
+
+    #[test]
+    fn strategies_agree_on_random_lines() {
+        let mut rng = Rng::new(2023);
+
+        for (name, vocabulary) in vocabularies() {
+            let lines: Vec<String> = (0..500).map(|_| random_line(&mut rng)).collect();
+            assert_agree(name, &vocabulary, lines.iter().map(String::as_str))
+        }
+    }
+
+    macro_rules! calibration_value_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (line, expected) = $value;
+                    let vocabulary = DigitVocabulary::english();
+                    for (strategy, extractor) in extractors(&vocabulary) {
+                        assert_eq!(expected, extractor.calibration_value(line), "{}", strategy)
+                    }
+                    assert_eq!(expected, vocabulary.calibration_value(line))
+                }
+            )*
+        }
+    }
+
+    calibration_value_tests! {
+        eightwothree: ("eightwothree", 83),
+        eightoneight: ("eightoneight", 88),
+        oneight: ("oneight", 18),
+        twone_with_digit_between: ("xtwone3four", 24),
+        no_digits: ("nothing", 0),
+    }
+
+    #[test]
+    fn longest_pattern_wins_at_shared_start() {
+        let vocabulary =
+            DigitVocabulary::from_table(&[("ab", 1), ("abc", 2), ("b", 3), ("bc", 4)]).unwrap();
+
+        for (strategy, extractor) in extractors(&vocabulary) {
+            let first = extractor.first_match("xabcx").unwrap();
+            let last = extractor.last_match("xabcx").unwrap();
+
+            assert_eq!(
+                (1, 4, 2),
+                (first.start, first.end, first.value),
+                "{}",
+                strategy
+            );
+            assert_eq!(
+                (2, 4, 4),
+                (last.start, last.end, last.value),
+                "{}",
+                strategy
+            )
+        }
+    }
+
+    #[test]
+    fn naive_matches_lists_every_start() {
+        let extractor = NaiveExtractor::new(&DigitVocabulary::english());
+
+        let starts: Vec<usize> = extractor
+            .matches("eightwothree")
+            .iter()
+            .map(|m| m.start)
+            .collect();
+
+        assert_eq!(vec![0, 4, 7], starts)
+    }
+}
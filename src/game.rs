@@ -1,135 +1,388 @@
-use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
 
-use regex::Regex;
+use crate::error::{offset_of, AocError};
 
-#[derive(PartialEq, Debug)]
-pub struct CubesPulled {
-    pub id: i32,
-    pub blue: i32,
-    pub green: i32,
-    pub red: i32,
+const STANDARD_NAMES: [&str; 3] = ["red", "green", "blue"];
+
+// A cube colour. The three colours of the puzzle are plain values; any other
+// name is shared between copies of the colour and freed with the last one.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Colour(Name);
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Name {
+    Standard(u8),
+    Other(Arc<str>),
+}
+
+impl Colour {
+    pub const RED: Colour = Colour(Name::Standard(0));
+    pub const GREEN: Colour = Colour(Name::Standard(1));
+    pub const BLUE: Colour = Colour(Name::Standard(2));
+    pub const STANDARD: [Colour; 3] = [Colour::RED, Colour::GREEN, Colour::BLUE];
+
+    pub fn named(name: &str) -> Self {
+        match STANDARD_NAMES.iter().position(|n| *n == name) {
+            Some(i) => Colour(Name::Standard(i as u8)),
+            None => Colour(Name::Other(Arc::from(name))),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match &self.0 {
+            Name::Standard(i) => STANDARD_NAMES[*i as usize],
+            Name::Other(name) => name,
+        }
+    }
+}
+
+impl fmt::Debug for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Colour({})", self.name())
+    }
+}
+
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Draw {
+    pub count: u32,
+    pub colour: Colour,
+}
+
+// One handful of cubes, with its draws in the order they were written so
+// the line can be reproduced exactly.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Round {
+    pub draws: Vec<Draw>,
 }
 
-fn get_game_and_input(input: &str) -> (i32, &str) {
-    let mut parts = input.split(":");
-    let game_part = parts.next().unwrap();
-    let rest = parts.next().unwrap();
-    let id = game_part.replace("Game ", "").parse::<i32>().unwrap();
+// How many cubes of each colour a bag holds, in the order the colours were
+// added. Colours that were never added hold none.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Bag {
+    counts: Vec<(Colour, u32)>,
+}
+
+// A colour a game needed more of than the bag held.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortfall {
+    pub colour: Colour,
+    pub needed: u32,
+    pub available: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+// What to do with a colour the parser was not told about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownColours {
+    #[default]
+    Error,
+    // Drop the draw. The game no longer displays as the original line.
+    Ignore,
+    // Keep the draw under its own colour.
+    Include,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameParser {
+    known: Vec<Colour>,
+    unknown: UnknownColours,
+}
+
+pub fn try_get_game_and_input(input: &str) -> Result<(u32, &str), AocError> {
+    let (game_part, rest) = input
+        .split_once(':')
+        .ok_or_else(|| AocError::parse(input.len() + 1, "expected ':' after game id"))?;
+    let id_str = game_part
+        .strip_prefix("Game ")
+        .ok_or_else(|| AocError::parse(1, "expected line to start with 'Game '"))?;
+    let id = id_str
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| AocError::parse(6, format!("invalid game id '{}'", id_str)))?;
+
+    Ok((id, rest.trim()))
+}
 
-    (id, rest.trim())
+fn get_individual_runs(input: &str) -> Vec<&str> {
+    input.split(';').map(|str| str.trim()).collect()
 }
 
-fn get_individual_runs(input: &str) -> Vec<String> {
-    let parts = input.split(";");
-    parts
-        .into_iter()
-        .map(|str| str.trim().to_string())
-        .collect()
+impl Default for GameParser {
+    fn default() -> Self {
+        Self::new(Colour::STANDARD)
+    }
 }
 
-pub fn map_input_to_cubes_puled(input: &str) -> Vec<CubesPulled> {
-    let (id, rest) = get_game_and_input(input);
-    let parts = get_individual_runs(rest);
+impl GameParser {
+    pub fn new(known: impl IntoIterator<Item = Colour>) -> Self {
+        Self {
+            known: known.into_iter().collect(),
+            unknown: UnknownColours::default(),
+        }
+    }
+
+    pub fn with_unknown_colours(self, unknown: UnknownColours) -> Self {
+        Self { unknown, ..self }
+    }
+
+    fn colour(&self, name: &str) -> Option<Colour> {
+        self.known.iter().find(|c| c.name() == name).cloned()
+    }
+
+    pub fn parse_round(&self, input: &str) -> Result<Round, AocError> {
+        let mut draws: Vec<Draw> = vec![];
+
+        for item in input.split(',') {
+            let item = item.trim();
+            let column = offset_of(input, item) + 1;
+            let (count, name) = item
+                .split_once(' ')
+                .ok_or_else(|| AocError::parse(column, "expected '<count> <colour>'"))?;
+            let count = count
+                .parse::<u32>()
+                .map_err(|_| AocError::parse(column, format!("invalid cube count '{}'", count)))?;
+            let name = name.trim();
+
+            let colour = match (self.colour(name), self.unknown) {
+                (Some(colour), _) => colour,
+                (None, UnknownColours::Error) => {
+                    return Err(AocError::parse(
+                        offset_of(input, name) + 1,
+                        format!("unknown colour '{}'", name),
+                    ))
+                }
+                (None, UnknownColours::Ignore) => continue,
+                (None, UnknownColours::Include) => Colour::named(name),
+            };
+
+            // A colour may be drawn more than once; its total must still fit.
+            let total = draws
+                .iter()
+                .filter(|d| d.colour == colour)
+                .try_fold(count, |total, d| total.checked_add(d.count));
+            if total.is_none() {
+                return Err(AocError::parse(
+                    column,
+                    format!("too many {} cubes in one round", name),
+                ));
+            }
+
+            draws.push(Draw { count, colour });
+        }
+
+        Ok(Round { draws })
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Game, AocError> {
+        let (id, rest) = try_get_game_and_input(input)?;
+
+        let rounds = get_individual_runs(rest)
+            .iter()
+            .map(|part| {
+                self.parse_round(part)
+                    .map_err(|e| e.with_offset(offset_of(input, part)))
+            })
+            .collect::<Result<Vec<Round>, AocError>>()?;
+
+        Ok(Game { id, rounds })
+    }
+}
 
-    parts
-        .iter()
-        .map(|part| CubesPulled::from_string(id, part))
-        .collect()
+impl Round {
+    pub fn count(&self, colour: &Colour) -> u32 {
+        self.draws
+            .iter()
+            .filter(|d| &d.colour == colour)
+            .map(|d| d.count)
+            .sum()
+    }
 }
 
-fn get_highest_value(input: (i32, i32)) -> i32 {
-    match input {
-        (0, 0) => 0,
-        (0, x) => x,
-        (x, 0) => x,
-        (x, y) => {
-            if x < y {
-                y
-            } else {
-                x
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, draw) in self.draws.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
             }
+            write!(f, "{} {}", draw.count, draw.colour)?;
         }
+        Ok(())
     }
 }
 
-impl CubesPulled {
-    fn new(id: i32, r: i32, g: i32, b: i32) -> Self {
-        Self {
-            id,
-            red: r,
-            green: g,
-            blue: b,
+impl Bag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, colour: Colour, count: u32) -> Self {
+        match self.counts.iter_mut().find(|(c, _)| *c == colour) {
+            Some((_, existing)) => *existing = count,
+            None => self.counts.push((colour, count)),
         }
+        self
+    }
+
+    pub fn count(&self, colour: &Colour) -> u32 {
+        self.counts
+            .iter()
+            .find(|(c, _)| c == colour)
+            .map_or(0, |(_, count)| *count)
     }
 
-    pub fn collect_max(cubes: Vec<CubesPulled>) -> Self {
-        let (r, g, b) = cubes.iter().fold((0, 0, 0), |aggregate, item| {
-            return (
-                if aggregate.0 > item.red {
-                    aggregate.0
-                } else {
-                    item.red
-                },
-                if aggregate.1 > item.green {
-                    aggregate.1
-                } else {
-                    item.green
-                },
-                if aggregate.2 > item.blue {
-                    aggregate.2
-                } else {
-                    item.blue
-                },
-            );
-        });
+    pub fn counts(&self) -> impl Iterator<Item = (&Colour, u32)> + '_ {
+        self.counts.iter().map(|(colour, count)| (colour, *count))
+    }
 
-        Self {
-            id: cubes[0].id,
-            red: r,
-            green: g,
-            blue: b,
+    // The product of the counts of every colour in the bag; an empty bag
+    // has no power at all.
+    pub fn power(&self) -> u64 {
+        match self.counts.is_empty() {
+            true => 0,
+            false => self.counts.iter().map(|(_, count)| *count as u64).product(),
         }
     }
+}
 
-    pub fn collect_min(cubes: Vec<CubesPulled>) -> Self {
-        let (r, g, b) = cubes.iter().fold((0, 0, 0), |aggregate, item| {
-            let rr = (aggregate.0, item.red);
-            let gg = (aggregate.1, item.green);
-            let bb = (aggregate.2, item.blue);
+// Reads a bag description such as "12 red, 13 green, 14 blue". Any colour
+// name is accepted.
+impl FromStr for Bag {
+    type Err = AocError;
 
-            return (
-                get_highest_value(rr),
-                get_highest_value(gg),
-                get_highest_value(bb),
-            );
-        });
+    fn from_str(input: &str) -> Result<Self, AocError> {
+        let round = GameParser::default()
+            .with_unknown_colours(UnknownColours::Include)
+            .parse_round(input)?;
 
-        Self {
-            id: cubes[0].id,
-            red: r,
-            green: g,
-            blue: b,
+        Ok(round.draws.iter().fold(Bag::new(), |bag, d| {
+            bag.with(d.colour.clone(), round.count(&d.colour))
+        }))
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (colour, count)) in self.counts.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, colour)?;
         }
+        Ok(())
     }
+}
 
-    pub fn from_string(id: i32, input: &str) -> Self {
-        let pattern = r"(\d+) (\w+)";
-        let re = Regex::new(pattern).unwrap();
-        let map: HashMap<String, i32> = re
-            .captures_iter(input)
-            .map(|m| (m[2].to_string(), m[1].parse::<i32>().unwrap_or(0)))
-            .collect();
+impl Shortfall {
+    pub fn excess(&self) -> u32 {
+        self.needed - self.available
+    }
+}
 
-        Self::new(
-            id,
-            map.get("red").copied().unwrap_or(0),
-            map.get("green").copied().unwrap_or(0),
-            map.get("blue").copied().unwrap_or(0),
+impl fmt::Display for Shortfall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({} over)",
+            self.needed,
+            self.colour,
+            self.excess()
         )
     }
+}
+
+impl Game {
+    pub fn round_count(&self) -> usize {
+        self.rounds.len()
+    }
+
+    // The most cubes of `colour` shown in any one round.
+    pub fn max_count(&self, colour: &Colour) -> u32 {
+        self.rounds
+            .iter()
+            .map(|r| r.count(colour))
+            .max()
+            .unwrap_or(0)
+    }
+
+    // The smallest bag every round could have been drawn from, holding the
+    // colours the game shows in the order they first appear.
+    pub fn minimum_bag(&self) -> Bag {
+        let mut colours: Vec<Colour> = vec![];
+        for draw in self.rounds.iter().flat_map(|r| r.draws.iter()) {
+            if !colours.contains(&draw.colour) {
+                colours.push(draw.colour.clone());
+            }
+        }
+
+        colours.into_iter().fold(Bag::new(), |bag, c| {
+            let count = self.max_count(&c);
+            bag.with(c, count)
+        })
+    }
+
+    pub fn shortfalls(&self, bag: &Bag) -> Vec<Shortfall> {
+        self.minimum_bag()
+            .counts()
+            .filter(|(colour, needed)| *needed > bag.count(colour))
+            .map(|(colour, needed)| Shortfall {
+                colour: colour.clone(),
+                needed,
+                available: bag.count(colour),
+            })
+            .collect()
+    }
 
-    pub fn pow(&self) -> i32 {
-        self.red * self.blue * self.green
+    pub fn is_possible_with(&self, bag: &Bag) -> bool {
+        self.shortfalls(bag).is_empty()
+    }
+
+    // The puzzle's power: red times green times blue, so a game that never
+    // shows one of them has no power.
+    pub fn power(&self) -> u64 {
+        Colour::STANDARD
+            .iter()
+            .map(|c| self.max_count(c) as u64)
+            .product()
+    }
+
+    // The product over every colour the game showed, whatever the palette.
+    pub fn shown_power(&self) -> u64 {
+        self.minimum_bag().power()
+    }
+}
+
+impl FromStr for Game {
+    type Err = AocError;
+
+    // Reads red, green and blue cubes and rejects any other colour.
+    fn from_str(input: &str) -> Result<Self, AocError> {
+        GameParser::default().parse(input)
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (i, round) in self.rounds.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", round)?;
+        }
+        Ok(())
     }
 }
 
@@ -144,7 +397,7 @@ mod game_tests {
         fn $name() {
             let number = $value;
             let input = format!("Game {}: 3 blue, 4 red", number);
-            let (id, _) = get_game_and_input(&input);
+            let (id, _) = try_get_game_and_input(&input).unwrap();
             assert_eq!(number, id)
         }
     )*
@@ -160,7 +413,7 @@ mod game_tests {
     #[test]
     fn get_game_and_input_gets_correct_id() {
         let input = "Game 1: 3 blue, 4 red";
-        let (id, _) = get_game_and_input(input);
+        let (id, _) = try_get_game_and_input(input).unwrap();
 
         assert_eq!(1, id)
     }
@@ -168,7 +421,7 @@ mod game_tests {
     #[test]
     fn get_game_and_input_gets_correct_remainder() {
         let input = "Game 1: 3 blue, 4 red";
-        let (_, rest) = get_game_and_input(input);
+        let (_, rest) = try_get_game_and_input(input).unwrap();
 
         assert_eq!("3 blue, 4 red", rest)
     }
@@ -194,28 +447,240 @@ mod game_tests {
         assert_eq!(expected, collection)
     }
 
+    fn game(line: &str) -> Game {
+        line.parse().unwrap()
+    }
+
     #[test]
-    fn cubes_pulled_from_string_single_input_returns_correct_cube() {
-        let input = "3 blue, 4 red, 1 green";
-        let id = 5;
-        let expected = CubesPulled::new(id, 4, 1, 3);
+    fn round_from_string_keeps_draw_order() {
+        let round = GameParser::default()
+            .parse_round("3 blue, 4 red, 1 green")
+            .unwrap();
+
+        assert_eq!(
+            (4, 1, 3),
+            (
+                round.count(&Colour::RED),
+                round.count(&Colour::GREEN),
+                round.count(&Colour::BLUE)
+            )
+        );
+        assert_eq!(Colour::BLUE, round.draws[0].colour)
+    }
 
-        let collection = CubesPulled::from_string(id, input);
+    #[test]
+    fn game_from_str_reads_every_round() {
+        let game = game("Game 5: 13 blue, 4 red, 1 green; 1 blue, 20 red");
+
+        assert_eq!(5, game.id);
+        assert_eq!(2, game.round_count());
+        assert_eq!(20, game.max_count(&Colour::RED));
+        assert_eq!(1, game.max_count(&Colour::GREEN));
+        assert_eq!(13, game.max_count(&Colour::BLUE))
+    }
 
-        assert_eq!(expected, collection)
+    macro_rules! display_round_trip_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let line = $value;
+                    assert_eq!(line, game(line).to_string())
+                }
+            )*
+        }
+    }
+
+    display_round_trip_tests! {
+        round_trip_example_1: "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        round_trip_example_3: "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        round_trip_single_draw: "Game 100: 1 red",
+        round_trip_repeated_colour: "Game 7: 2 blue, 3 blue",
     }
 
     #[test]
-    fn map_input_to_cubes_puled_input_string_produces_correct_output() {
-        let input = "Game 5: 13 blue, 4 red, 1 green; 1 blue, 20 red";
-        let id = 5;
-        let expected_collection = vec![
-            CubesPulled::new(id, 4, 1, 13),
-            CubesPulled::new(id, 20, 0, 1),
-        ];
+    fn minimum_bag_and_power() {
+        let game = game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+
+        let bag = game.minimum_bag();
+
+        assert_eq!(
+            (4, 2, 6),
+            (
+                bag.count(&Colour::RED),
+                bag.count(&Colour::GREEN),
+                bag.count(&Colour::BLUE)
+            )
+        );
+        assert_eq!(48, game.power())
+    }
+
+    #[test]
+    fn missing_colour_leaves_no_power() {
+        let game = game("Game 1: 3 red, 4 blue");
+
+        assert_eq!(0, game.power());
+        assert_eq!(12, game.shown_power())
+    }
+
+    #[test]
+    fn feasibility_against_bag() {
+        let bag = Bag::new()
+            .with(Colour::RED, 12)
+            .with(Colour::GREEN, 13)
+            .with(Colour::BLUE, 14);
+
+        assert!(game("Game 1: 3 blue, 4 red; 2 green").is_possible_with(&bag));
+        assert!(!game("Game 3: 8 green, 6 blue, 20 red").is_possible_with(&bag));
+        assert!(game("Game 2: 12 red, 13 green, 14 blue").is_possible_with(&bag))
+    }
+
+    fn parse_with(unknown: UnknownColours, line: &str) -> Result<Game, AocError> {
+        GameParser::default()
+            .with_unknown_colours(unknown)
+            .parse(line)
+    }
+
+    #[test]
+    fn unknown_colours_can_be_ignored() {
+        let game = parse_with(UnknownColours::Ignore, "Game 1: 3 blue, 2 mauve; 1 red").unwrap();
+
+        assert_eq!("Game 1: 3 blue; 1 red", game.to_string());
+        assert_eq!(3, game.shown_power())
+    }
+
+    #[test]
+    fn unknown_colours_can_be_included() {
+        let line = "Game 1: 3 blue, 2 teal; 5 teal, 2 red";
+
+        let game = parse_with(UnknownColours::Include, line).unwrap();
+        let teal = Colour::named("teal");
 
-        let collection = map_input_to_cubes_puled(input);
+        assert_eq!(line, game.to_string());
+        assert_eq!(5, game.max_count(&teal));
+        assert_eq!(3 * 5 * 2, game.shown_power());
+        assert_eq!(0, game.power())
+    }
+
+    #[test]
+    fn unknown_colours_are_errors_by_default() {
+        assert!(parse_with(UnknownColours::Error, "Game 1: 3 blue, 2 ochre").is_err());
+        assert!("Game 1: 3 blue, 2 ochre".parse::<Game>().is_err())
+    }
+
+    #[test]
+    fn parser_accepts_a_custom_palette() {
+        let palette = ["cyan", "magenta", "yellow"].map(Colour::named);
+        let parser = GameParser::new(palette.clone());
+
+        let game = parser.parse("Game 4: 2 cyan, 3 magenta; 4 yellow").unwrap();
+        let bag = Bag::new()
+            .with(palette[0].clone(), 2)
+            .with(palette[1].clone(), 3)
+            .with(palette[2].clone(), 3);
+
+        assert_eq!(24, game.shown_power());
+        assert_eq!(0, game.power());
+        assert!(!game.is_possible_with(&bag));
+        assert!(parser.parse("Game 4: 1 red").is_err())
+    }
+
+    #[test]
+    fn minimum_bag_lists_colours_in_order_of_appearance() {
+        let game = game("Game 2: 1 green; 3 blue, 4 green; 2 red");
+
+        let bag = game.minimum_bag();
+        let counts: Vec<(&str, u32)> = bag.counts().map(|(c, n)| (c.name(), n)).collect();
+
+        assert_eq!(vec![("green", 4), ("blue", 3), ("red", 2)], counts)
+    }
+
+    #[test]
+    fn the_same_name_gives_the_same_colour() {
+        assert_eq!(Colour::RED, Colour::named("red"));
+        assert_eq!(Colour::named("violet"), Colour::named("violet"));
+        assert_ne!(Colour::named("violet"), Colour::named("indigo"))
+    }
+
+    #[test]
+    fn bag_round_trips_through_text() {
+        let bag: Bag = "12 red, 13 green, 14 blue, 2 gold".parse().unwrap();
+
+        assert_eq!(12, bag.count(&Colour::RED));
+        assert_eq!(2, bag.count(&Colour::named("gold")));
+        assert_eq!("12 red, 13 green, 14 blue, 2 gold", bag.to_string())
+    }
+
+    #[test]
+    fn malformed_bag_is_an_error() {
+        assert!("".parse::<Bag>().is_err());
+        assert!("12 red, lots green".parse::<Bag>().is_err());
+        assert!("4294967295 red, 1 red".parse::<Bag>().is_err())
+    }
+
+    #[test]
+    fn shortfalls_report_every_colour_over_the_limit() {
+        let bag: Bag = "12 red, 13 green, 14 blue".parse().unwrap();
+        let game = game("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 15 green");
+
+        let shortfalls: Vec<String> = game
+            .shortfalls(&bag)
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(vec!["15 green (2 over)", "20 red (8 over)"], shortfalls)
+    }
+
+    #[test]
+    fn colour_missing_from_bag_is_a_shortfall() {
+        let bag: Bag = "5 red".parse().unwrap();
+
+        let shortfalls = game("Game 1: 1 red, 1 blue").shortfalls(&bag);
+
+        assert_eq!(1, shortfalls.len());
+        assert_eq!(
+            (Colour::BLUE, 1),
+            (shortfalls[0].colour.clone(), shortfalls[0].excess())
+        )
+    }
+
+    #[test]
+    fn empty_game_needs_an_empty_bag() {
+        let game = Game {
+            id: 1,
+            rounds: vec![],
+        };
+
+        assert_eq!(0, game.max_count(&Colour::RED));
+        assert_eq!(0, game.power());
+        assert!(game.is_possible_with(&Bag::new()))
+    }
+
+    macro_rules! game_from_str_error_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (input, expected_column) = $value;
+                    let result = input.parse::<Game>();
+
+                    match result {
+                        Err(AocError::Parse { column, .. }) => assert_eq!(expected_column, column),
+                        other => panic!("expected parse error, got {:?}", other),
+                    }
+                }
+            )*
+        }
+    }
 
-        assert_eq!(expected_collection, collection)
+    game_from_str_error_tests! {
+        missing_colon_is_error: ("Game 1 3 blue", 14),
+        missing_game_prefix_is_error: ("Round 1: 3 blue", 1),
+        invalid_id_is_error: ("Game x: 3 blue", 6),
+        missing_colour_is_error: ("Game 1: 3 blue; 4", 17),
+        invalid_count_is_error: ("Game 1: 3 blue, lots red", 17),
+        unknown_colour_is_error: ("Game 1: 3 blue, 2 purple", 19),
+        overflowing_repeated_colour_is_error: ("Game 1: 4294967295 red, 1 red", 25),
     }
 }
@@ -0,0 +1,226 @@
+// A single pass over a day 2 line that keeps only what the puzzle needs: the
+// id and the most cubes of each standard colour shown in any round. It
+// borrows the line and never allocates, while rejecting the same malformed
+// lines as `GameParser`.
+
+use crate::error::AocError;
+use crate::game::{Bag, Colour};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameTally {
+    pub id: u32,
+    // Indexed like `Colour::STANDARD`.
+    most: [u32; 3],
+}
+
+impl GameTally {
+    pub fn max_count(&self, colour: &Colour) -> u32 {
+        Colour::STANDARD
+            .iter()
+            .position(|c| c == colour)
+            .map_or(0, |i| self.most[i])
+    }
+
+    pub fn is_possible_with(&self, bag: &Bag) -> bool {
+        Colour::STANDARD
+            .iter()
+            .zip(self.most)
+            .all(|(colour, most)| most <= bag.count(colour))
+    }
+
+    // Red times green times blue, as `Game::power`.
+    pub fn power(&self) -> u64 {
+        self.most.iter().map(|m| *m as u64).product()
+    }
+}
+
+struct Cursor<'a> {
+    line: &'a str,
+    at: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.line.as_bytes().get(self.at).copied()
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.at += 1;
+        }
+    }
+
+    // Everything up to the next space or separator.
+    fn word(&mut self) -> &'a str {
+        let start = self.at;
+        while self
+            .peek()
+            .is_some_and(|b| !b.is_ascii_whitespace() && b != b',' && b != b';')
+        {
+            self.at += 1;
+        }
+        &self.line[start..self.at]
+    }
+
+    fn column(&self) -> usize {
+        self.at + 1
+    }
+}
+
+fn standard_colour(name: &str) -> Option<usize> {
+    match name {
+        "red" => Some(0),
+        "green" => Some(1),
+        "blue" => Some(2),
+        _ => None,
+    }
+}
+
+pub fn scan_game(line: &str) -> Result<GameTally, AocError> {
+    let Some(colon) = line.find(':') else {
+        return Err(AocError::parse(
+            line.len() + 1,
+            "expected ':' after game id",
+        ));
+    };
+    if !line.starts_with("Game ") {
+        return Err(AocError::parse(1, "expected line to start with 'Game '"));
+    }
+    let id_str = &line[5..colon];
+    let id = id_str
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| AocError::parse(6, format!("invalid game id '{}'", id_str)))?;
+    let mut cursor = Cursor {
+        line,
+        at: colon + 1,
+    };
+
+    let mut tally = GameTally { id, most: [0; 3] };
+
+    loop {
+        cursor.skip_spaces();
+        let item = cursor.column();
+        let count = cursor.word();
+        let count = match count.is_empty() {
+            true => return Err(AocError::parse(item, "expected '<count> <colour>'")),
+            false => count
+                .parse::<u32>()
+                .map_err(|_| AocError::parse(item, format!("invalid cube count '{}'", count)))?,
+        };
+
+        cursor.skip_spaces();
+        let name_column = cursor.column();
+        let name = cursor.word();
+        if name.is_empty() {
+            return Err(AocError::parse(item, "expected '<count> <colour>'"));
+        }
+        let slot = standard_colour(name)
+            .ok_or_else(|| AocError::parse(name_column, format!("unknown colour '{}'", name)))?;
+        tally.most[slot] = tally.most[slot].max(count);
+
+        cursor.skip_spaces();
+        match cursor.peek() {
+            None => return Ok(tally),
+            Some(b',') | Some(b';') => cursor.at += 1,
+            Some(_) => {
+                return Err(AocError::parse(
+                    cursor.column(),
+                    "expected ',' or ';' between draws",
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod game_scan_tests {
+    use super::*;
+    use crate::game::Game;
+    use crate::game_simulator::GameSimulator;
+
+    #[test]
+    fn scan_matches_parsed_games() {
+        let document = GameSimulator::new(25).with_games(500).generate();
+        let bag: Bag = "12 red, 13 green, 14 blue".parse().unwrap();
+
+        for line in document.lines.iter() {
+            let game: Game = line.parse().unwrap();
+            let tally = scan_game(line).unwrap();
+
+            assert_eq!(game.id, tally.id);
+            for colour in Colour::STANDARD {
+                assert_eq!(
+                    game.max_count(&colour),
+                    tally.max_count(&colour),
+                    "{}",
+                    line
+                );
+            }
+            assert_eq!(game.power(), tally.power(), "{}", line);
+            assert_eq!(game.is_possible_with(&bag), tally.is_possible_with(&bag))
+        }
+    }
+
+    macro_rules! scan_game_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (input, expected): (&str, (u32, [u32; 3], u64)) = $value;
+                    let tally = scan_game(input).unwrap();
+                    let most = Colour::STANDARD.map(|c| tally.max_count(&c));
+
+                    assert_eq!(expected, (tally.id, most, tally.power()));
+                    assert_eq!(input.parse::<Game>().unwrap().power(), tally.power())
+                }
+            )*
+        }
+    }
+
+    scan_game_tests! {
+        example: ("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", (1, [4, 2, 6], 48)),
+        extra_spaces: ("Game  12 :  3 blue ,4 red;1 red", (12, [4, 0, 3], 0)),
+        shown_zero_makes_power_zero: ("Game 3: 0 red, 5 blue", (3, [0, 0, 5], 0)),
+        single_draw: ("Game 100: 20 green", (100, [0, 20, 0], 0)),
+    }
+
+    macro_rules! scan_game_error_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let input = $value;
+
+                    match (scan_game(input), input.parse::<Game>()) {
+                        (
+                            Err(AocError::Parse { column, .. }),
+                            Err(AocError::Parse { column: expected, .. }),
+                        ) => assert_eq!(expected, column),
+                        other => panic!("expected parse errors, got {:?}", other),
+                    }
+                }
+            )*
+        }
+    }
+
+    scan_game_error_tests! {
+        missing_colon_is_error: "Game 1 3 blue",
+        missing_game_prefix_is_error: "Round 1: 3 blue",
+        invalid_id_is_error: "Game x: 3 blue",
+        missing_id_is_error: "Game : 3 blue",
+        missing_colour_is_error: "Game 1: 3 blue; 4",
+        invalid_count_is_error: "Game 1: 3 blue, lots red",
+        overflowing_count_is_error: "Game 1: 99999999999 blue",
+        unknown_colour_is_error: "Game 1: 3 blue, 2 purple",
+        no_rounds_is_error: "Game 1:",
+        empty_round_is_error: "Game 1: 3 blue;; 2 red",
+        trailing_separator_is_error: "Game 1: 3 blue;",
+    }
+
+    #[test]
+    fn words_after_a_colour_are_rejected() {
+        assert!(scan_game("Game 1: 3 blue cubes").is_err());
+        assert!("Game 1: 3 blue cubes".parse::<Game>().is_err())
+    }
+}
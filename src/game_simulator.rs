@@ -0,0 +1,264 @@
+use std::ops::RangeInclusive;
+
+use crate::document::Document;
+use crate::game::{Bag, Colour};
+use crate::rng::Rng;
+
+// The bag day 2 part 1 checks games against, kept apart from the solver's
+// copy so the expected answers do not depend on it.
+const ELF_LIMITS: [(Colour, u32); 3] = [(Colour::RED, 12), (Colour::GREEN, 13), (Colour::BLUE, 14)];
+
+// Integer weights over how many rounds a game has.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundCounts {
+    weights: Vec<(usize, u32)>,
+    total: u32,
+}
+
+impl RoundCounts {
+    pub fn fixed(rounds: usize) -> Self {
+        Self::from_weights([(rounds, 1)])
+    }
+
+    pub fn uniform(rounds: RangeInclusive<usize>) -> Self {
+        Self::from_weights(rounds.map(|r| (r, 1)))
+    }
+
+    pub fn from_weights(weights: impl IntoIterator<Item = (usize, u32)>) -> Self {
+        let weights: Vec<(usize, u32)> = weights.into_iter().filter(|(_, w)| *w > 0).collect();
+        assert!(
+            weights.iter().all(|(rounds, _)| *rounds > 0),
+            "every game needs at least one round"
+        );
+        let total = weights.iter().map(|(_, w)| w).sum();
+        assert!(total > 0, "round counts need some weight");
+
+        Self { weights, total }
+    }
+
+    fn sample(&self, rng: &mut Rng) -> usize {
+        let mut pick = rng.below(self.total as usize) as u32;
+        for (rounds, weight) in self.weights.iter() {
+            if pick < *weight {
+                return *rounds;
+            }
+            pick -= weight;
+        }
+        unreachable!("the pick is below the total weight")
+    }
+}
+
+// Plays day 2 games against a known bag: each round the elf grabs a handful
+// of cubes, shows them and puts them back. The expected answers are tallied
+// while drawing rather than by parsing the lines back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSimulator {
+    seed: u64,
+    games: usize,
+    bag: Bag,
+    rounds: RoundCounts,
+}
+
+impl GameSimulator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            games: 100,
+            // A little bigger than the elf's bag, so some games are impossible.
+            bag: Bag::new()
+                .with(Colour::RED, 15)
+                .with(Colour::GREEN, 16)
+                .with(Colour::BLUE, 17),
+            rounds: RoundCounts::uniform(1..=6),
+        }
+    }
+
+    pub fn with_games(self, games: usize) -> Self {
+        Self { games, ..self }
+    }
+
+    // Only red, green and blue cubes, since those are all day 2 accepts.
+    pub fn with_bag(self, bag: Bag) -> Self {
+        for (colour, _) in bag.counts() {
+            assert!(
+                Colour::STANDARD.contains(colour),
+                "the bag can only hold red, green and blue cubes, got {}",
+                colour
+            );
+        }
+        assert!(
+            bag.counts().any(|(_, count)| count > 0),
+            "the bag needs at least one cube"
+        );
+        Self { bag, ..self }
+    }
+
+    pub fn with_rounds(self, rounds: RoundCounts) -> Self {
+        Self { rounds, ..self }
+    }
+
+    pub fn generate(&self) -> Document {
+        let mut rng = Rng::new(self.seed);
+        let cubes: Vec<Colour> = self
+            .bag
+            .counts()
+            .flat_map(|(colour, count)| (0..count).map(move |_| colour.clone()))
+            .collect();
+
+        let mut document = Document::with_capacity(self.games);
+
+        for id in 1..=self.games as u32 {
+            let mut line = format!("Game {}: ", id);
+            let mut most = [0; 3];
+
+            for round in 0..self.rounds.sample(&mut rng) {
+                if round > 0 {
+                    line.push_str("; ");
+                }
+
+                let shown = handful(&mut rng, &cubes);
+                for (i, (colour, count)) in shown.iter().enumerate() {
+                    if i > 0 {
+                        line.push_str(", ");
+                    }
+                    line.push_str(&format!("{} {}", count, colour));
+
+                    let slot = Colour::STANDARD
+                        .iter()
+                        .position(|c| c == colour)
+                        .expect("the bag only holds standard colours");
+                    most[slot] = most[slot].max(*count);
+                }
+            }
+
+            if ELF_LIMITS
+                .iter()
+                .zip(most)
+                .all(|((_, limit), m)| m <= *limit)
+            {
+                document.part_1 += id as u64;
+            }
+            document.part_2 += most.iter().map(|m| *m as u64).product::<u64>();
+            document.lines.push(line);
+        }
+
+        document
+    }
+}
+
+// Draws between one cube and the whole bag without replacement, returning
+// each colour's count in the order it first came out.
+fn handful(rng: &mut Rng, cubes: &[Colour]) -> Vec<(Colour, u32)> {
+    let mut cubes = cubes.to_vec();
+    let size = 1 + rng.below(cubes.len());
+    let mut shown: Vec<(Colour, u32)> = vec![];
+
+    for i in 0..size {
+        let j = i + rng.below(cubes.len() - i);
+        cubes.swap(i, j);
+        match shown.iter_mut().find(|(c, _)| *c == cubes[i]) {
+            Some((_, count)) => *count += 1,
+            None => shown.push((cubes[i].clone(), 1)),
+        }
+    }
+
+    shown
+}
+
+#[cfg(test)]
+mod game_simulator_tests {
+    use super::*;
+    use crate::document::document_checks::{assert_answers, assert_seeded};
+    use crate::game::Game;
+    use crate::{solve_day_2_part_1, solve_day_2_part_2};
+
+    #[test]
+    fn answers_match_the_solvers() {
+        let cases = [
+            (1, "15 red, 16 green, 17 blue", RoundCounts::uniform(1..=6)),
+            (2, "12 red, 13 green, 14 blue", RoundCounts::fixed(3)),
+            (3, "40 red, 2 green, 30 blue", RoundCounts::uniform(1..=10)),
+            (4, "20 blue", RoundCounts::from_weights([(1, 5), (8, 1)])),
+        ];
+
+        for (seed, bag, rounds) in cases {
+            let document = GameSimulator::new(seed)
+                .with_games(300)
+                .with_bag(bag.parse().unwrap())
+                .with_rounds(rounds)
+                .generate();
+
+            assert_answers(
+                &document,
+                |lines| solve_day_2_part_1(lines),
+                |lines| solve_day_2_part_2(lines),
+            )
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_document() {
+        assert_seeded(|seed| GameSimulator::new(seed).with_games(50).generate())
+    }
+
+    #[test]
+    fn lines_round_trip_and_stay_inside_the_bag() {
+        let bag: Bag = "3 red, 1 green, 2 blue".parse().unwrap();
+        let document = GameSimulator::new(9)
+            .with_games(200)
+            .with_bag(bag.clone())
+            .generate();
+
+        for (i, line) in document.lines.iter().enumerate() {
+            let game: Game = line.parse().unwrap();
+            assert_eq!(i as u32 + 1, game.id);
+            assert_eq!(line, &game.to_string());
+            assert!(game.is_possible_with(&bag), "{}", line);
+        }
+    }
+
+    #[test]
+    fn the_elf_bag_makes_every_game_possible() {
+        let document = GameSimulator::new(5)
+            .with_games(100)
+            .with_bag("12 red, 13 green, 14 blue".parse().unwrap())
+            .generate();
+
+        assert_eq!((1..=100).sum::<u64>(), document.part_1)
+    }
+
+    #[test]
+    fn a_single_colour_bag_has_no_power() {
+        let document = GameSimulator::new(7)
+            .with_games(50)
+            .with_bag("20 blue".parse().unwrap())
+            .generate();
+
+        assert_eq!(0, document.part_2)
+    }
+
+    #[test]
+    fn round_counts_follow_the_distribution() {
+        let document = GameSimulator::new(6)
+            .with_games(100)
+            .with_rounds(RoundCounts::fixed(4))
+            .generate();
+
+        assert!(document
+            .lines
+            .iter()
+            .all(|line| line.parse::<Game>().unwrap().round_count() == 4))
+    }
+
+    #[test]
+    #[should_panic(expected = "the bag can only hold red, green and blue cubes")]
+    fn unknown_colours_are_rejected() {
+        GameSimulator::new(0).with_bag("3 red, 2 purple".parse().unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "every game needs at least one round")]
+    fn zero_rounds_are_rejected() {
+        RoundCounts::uniform(0..=2);
+    }
+}
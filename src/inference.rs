@@ -0,0 +1,510 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::error::AocError;
+use crate::game::{Bag, Colour, Game};
+
+// How each round was drawn. Either way the cubes go back into the bag
+// before the next round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sampling {
+    // Cubes are drawn one at a time and put back straight away, so every
+    // draw sees the whole bag.
+    WithReplacement,
+    // The whole handful is taken at once, so a round can never show more
+    // cubes of a colour than the bag holds.
+    WithoutReplacement,
+}
+
+// Prior weights over the total number of cubes in the bag. Given a size,
+// every way of splitting it between the game's colours is equally likely.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizePrior {
+    weights: Vec<(u32, f64)>,
+}
+
+impl SizePrior {
+    pub fn uniform(sizes: RangeInclusive<u32>) -> Self {
+        Self::from_weights(sizes.map(|size| (size, 1.0)))
+    }
+
+    pub fn from_weights(weights: impl IntoIterator<Item = (u32, f64)>) -> Self {
+        let weights = weights
+            .into_iter()
+            .inspect(|(size, weight)| {
+                assert!(
+                    *weight >= 0.0 && weight.is_finite(),
+                    "prior weight for size {} must be finite and non-negative, got {}",
+                    size,
+                    weight
+                )
+            })
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+
+        Self { weights }
+    }
+
+    fn max_size(&self) -> u32 {
+        self.weights
+            .iter()
+            .map(|(size, _)| *size)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+// The most bags a posterior may hold. The number of ways to split a bag
+// grows as size^(colours - 1), so wide priors over many colours are refused
+// rather than left to exhaust memory.
+const MAX_BAGS: f64 = (1 << 22) as f64;
+
+// ln(n!) for every n up to `max`, so binomials stay in log space.
+struct LogFactorials(Vec<f64>);
+
+impl LogFactorials {
+    fn new(max: u64) -> Self {
+        let mut table = vec![0.0];
+        for n in 1..=max {
+            table.push(table[n as usize - 1] + (n as f64).ln());
+        }
+        Self(table)
+    }
+
+    fn choose(&self, n: u64, k: u64) -> f64 {
+        match k <= n {
+            true => self.0[n as usize] - self.0[k as usize] - self.0[(n - k) as usize],
+            false => f64::NEG_INFINITY,
+        }
+    }
+
+    // ln of the number of ways to split `size` cubes between `parts` colours.
+    fn splits(&self, size: u32, parts: usize) -> f64 {
+        let parts = parts as u64;
+        self.choose(size as u64 + parts - 1, parts - 1)
+    }
+}
+
+// Visits every way to split `size` cubes between `parts` colours, in
+// lexicographic order, reusing one buffer.
+fn for_each_split(size: u32, parts: usize, mut visit: impl FnMut(&[u32])) {
+    let mut split = vec![0; parts];
+    split[parts - 1] = size;
+
+    'splits: loop {
+        visit(&split);
+
+        // Move one cube from the tail into the rightmost colour that can
+        // take it, and pile the rest of the tail into the last colour.
+        let mut rest = split[parts - 1];
+        for i in (0..parts - 1).rev() {
+            if rest > 0 {
+                split[i] += 1;
+                split[i + 1..].fill(0);
+                split[parts - 1] = rest - 1;
+                continue 'splits;
+            }
+            rest += split[i];
+        }
+        return;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Posterior {
+    colours: Vec<Colour>,
+    // Every bag with non-zero probability, as counts in `colours` order laid
+    // end to end, with the probability of each.
+    counts: Vec<u32>,
+    probabilities: Vec<f64>,
+}
+
+fn inference_error(game: &Game, message: impl fmt::Display) -> AocError {
+    AocError::Inference(format!("game {}: {}", game.id, message))
+}
+
+// The posterior over bags that could have produced `game`'s rounds. Only the
+// colours the game shows are modelled.
+pub fn infer(game: &Game, prior: &SizePrior, sampling: Sampling) -> Result<Posterior, AocError> {
+    let colours: Vec<Colour> = game
+        .minimum_bag()
+        .counts()
+        .map(|(c, _)| c.clone())
+        .collect();
+    if colours.is_empty() {
+        return Err(inference_error(game, "no cubes were shown"));
+    }
+
+    let parts = colours.len();
+    let factorials = LogFactorials::new(prior.max_size() as u64 + parts as u64);
+    let bags: f64 = prior
+        .weights
+        .iter()
+        .map(|(size, _)| factorials.splits(*size, parts).exp())
+        .sum();
+    if bags > MAX_BAGS {
+        return Err(inference_error(
+            game,
+            format!(
+                "the prior allows {:.0} bags of {} colours, more than the {} that can be enumerated",
+                bags, parts, MAX_BAGS
+            ),
+        ));
+    }
+
+    let rounds: Vec<Vec<u64>> = game
+        .rounds
+        .iter()
+        .map(|round| colours.iter().map(|c| round.count(c) as u64).collect())
+        .collect();
+
+    let log_likelihood = |bag: &[u32], size: u32| -> f64 {
+        rounds
+            .iter()
+            .map(|drawn| {
+                let handful: u64 = drawn.iter().sum();
+                match sampling {
+                    Sampling::WithReplacement => {
+                        drawn
+                            .iter()
+                            .zip(bag)
+                            .map(|(&c, &k)| match c {
+                                0 => 0.0,
+                                c => c as f64 * (k as f64).ln(),
+                            })
+                            .sum::<f64>()
+                            - handful as f64 * (size as f64).ln()
+                    }
+                    Sampling::WithoutReplacement => {
+                        drawn
+                            .iter()
+                            .zip(bag)
+                            .map(|(&c, &k)| factorials.choose(k as u64, c))
+                            .sum::<f64>()
+                            - factorials.choose(size as u64, handful)
+                    }
+                }
+            })
+            .sum()
+    };
+
+    let mut counts = vec![];
+    let mut probabilities = vec![];
+    for &(size, weight) in prior.weights.iter() {
+        let log_prior = weight.ln() - factorials.splits(size, parts);
+        for_each_split(size, parts, |bag| {
+            let log_posterior = log_prior + log_likelihood(bag, size);
+            if log_posterior.is_finite() {
+                counts.extend_from_slice(bag);
+                probabilities.push(log_posterior);
+            }
+        });
+    }
+
+    let max = probabilities
+        .iter()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+    if !max.is_finite() {
+        return Err(inference_error(
+            game,
+            "no bag the prior allows could have produced it",
+        ));
+    }
+    for p in probabilities.iter_mut() {
+        *p = (*p - max).exp();
+    }
+    let total: f64 = probabilities.iter().sum();
+    for p in probabilities.iter_mut() {
+        *p /= total;
+    }
+
+    Ok(Posterior {
+        colours,
+        counts,
+        probabilities,
+    })
+}
+
+impl Posterior {
+    pub fn colours(&self) -> &[Colour] {
+        &self.colours
+    }
+
+    fn bags(&self) -> impl Iterator<Item = (&[u32], f64)> {
+        self.counts
+            .chunks_exact(self.colours.len())
+            .zip(self.probabilities.iter().copied())
+    }
+
+    // Each bag's count of `colour` with the bag's probability, or None when
+    // the game never showed that colour.
+    fn counts_of(&self, colour: &Colour) -> Option<impl Iterator<Item = (u32, f64)> + '_> {
+        let i = self.colours.iter().position(|c| c == colour)?;
+        Some(self.bags().map(move |(bag, p)| (bag[i], p)))
+    }
+
+    // The probability of the bag holding exactly `k` cubes of `colour`, for
+    // every `k` from 0 up to the largest count with any weight.
+    pub fn marginal(&self, colour: &Colour) -> Option<Vec<f64>> {
+        let mut marginal = vec![];
+        for (k, p) in self.counts_of(colour)? {
+            let k = k as usize;
+            if marginal.len() <= k {
+                marginal.resize(k + 1, 0.0);
+            }
+            marginal[k] += p;
+        }
+        Some(marginal)
+    }
+
+    pub fn mean(&self, colour: &Colour) -> Option<f64> {
+        Some(self.counts_of(colour)?.map(|(k, p)| k as f64 * p).sum())
+    }
+
+    // The single most probable bag and its probability. Ties go to the bag
+    // from the size listed first in the prior.
+    pub fn most_likely(&self) -> (Bag, f64) {
+        let (bag, p) = self
+            .bags()
+            .fold(None, |best: Option<(&[u32], f64)>, candidate| match best {
+                Some(best) if best.1 >= candidate.1 => Some(best),
+                _ => Some(candidate),
+            })
+            .expect("a posterior always holds at least one bag");
+
+        let bag = self
+            .colours
+            .iter()
+            .zip(bag)
+            .fold(Bag::new(), |b, (c, k)| b.with(c.clone(), *k));
+        (bag, p)
+    }
+
+    // The equal-tailed interval holding `level` of the probability for
+    // `colour`, as inclusive bounds on its count.
+    pub fn credible_interval(&self, colour: &Colour, level: f64) -> Option<(u32, u32)> {
+        assert!(
+            (0.0..=1.0).contains(&level),
+            "credible level must be between 0 and 1, got {}",
+            level
+        );
+        let tail = (1.0 - level) / 2.0;
+        // Rounding in the normalisation must not push the upper bound past
+        // the last count with any weight.
+        let epsilon = 1e-12;
+
+        let marginal = self.marginal(colour)?;
+        let mut cumulative = 0.0;
+        let mut lower = None;
+        let mut upper = marginal.len() as u32 - 1;
+        for (k, p) in marginal.iter().enumerate() {
+            cumulative += p;
+            if lower.is_none() && cumulative > tail - epsilon && *p > 0.0 {
+                lower = Some(k as u32);
+            }
+            if cumulative >= 1.0 - tail - epsilon {
+                upper = k as u32;
+                break;
+            }
+        }
+
+        Some((lower.unwrap_or(upper), upper))
+    }
+}
+
+#[cfg(test)]
+mod inference_tests {
+    use super::*;
+    use crate::game::{GameParser, UnknownColours};
+
+    fn game(line: &str) -> Game {
+        line.parse().unwrap()
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {} got {}",
+            expected,
+            actual
+        )
+    }
+
+    #[test]
+    fn splits_cover_every_way_to_fill_the_bag() {
+        let splits = |size, parts| {
+            let mut all = vec![];
+            for_each_split(size, parts, |split| all.push(split.to_vec()));
+            all
+        };
+
+        assert_eq!(vec![vec![0, 2], vec![1, 1], vec![2, 0]], splits(2, 2));
+        assert_eq!(15, splits(4, 3).len());
+        assert_eq!(vec![vec![3]], splits(3, 1))
+    }
+
+    #[test]
+    fn without_replacement_a_full_handful_pins_the_bag() {
+        let game = game("Game 1: 3 red, 1 blue");
+
+        let posterior = infer(
+            &game,
+            &SizePrior::uniform(4..=4),
+            Sampling::WithoutReplacement,
+        )
+        .unwrap();
+        let (bag, p) = posterior.most_likely();
+
+        assert_eq!("3 red, 1 blue", bag.to_string());
+        assert_close(1.0, p)
+    }
+
+    #[test]
+    fn with_replacement_weights_bags_by_likelihood() {
+        let game = game("Game 1: 3 red, 1 blue");
+
+        let posterior =
+            infer(&game, &SizePrior::uniform(4..=4), Sampling::WithReplacement).unwrap();
+        let red = posterior.marginal(&Colour::RED).unwrap();
+
+        // (red, blue) = (1, 3), (2, 2), (3, 1) are proportional to
+        // 1^3 * 3, 2^3 * 2 and 3^3 * 1.
+        assert_close(0.0, red[0]);
+        assert_close(3.0 / 46.0, red[1]);
+        assert_close(16.0 / 46.0, red[2]);
+        assert_close(27.0 / 46.0, red[3])
+    }
+
+    #[test]
+    fn without_replacement_never_goes_below_the_minimum_bag() {
+        let game = game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+
+        let posterior = infer(
+            &game,
+            &SizePrior::uniform(12..=20),
+            Sampling::WithoutReplacement,
+        )
+        .unwrap();
+
+        let blue = posterior.marginal(&Colour::BLUE).unwrap();
+        assert!(blue[..6].iter().all(|p| *p == 0.0));
+        assert_close(1.0, blue.iter().sum());
+        let (lower, upper) = posterior.credible_interval(&Colour::BLUE, 0.9).unwrap();
+        assert!(6 <= lower && lower <= upper)
+    }
+
+    #[test]
+    fn with_replacement_allows_bags_smaller_than_a_handful() {
+        let game = game("Game 1: 5 red, 1 blue");
+
+        let posterior =
+            infer(&game, &SizePrior::uniform(2..=3), Sampling::WithReplacement).unwrap();
+
+        assert!(posterior.marginal(&Colour::RED).unwrap()[1] > 0.0);
+        assert!(infer(
+            &game,
+            &SizePrior::uniform(2..=3),
+            Sampling::WithoutReplacement
+        )
+        .is_err())
+    }
+
+    #[test]
+    fn prior_weights_shift_the_posterior() {
+        let game = game("Game 1: 1 red, 1 green");
+        let small = SizePrior::from_weights([(2, 9.0), (10, 1.0)]);
+        let large = SizePrior::from_weights([(2, 1.0), (10, 9.0)]);
+
+        let small = infer(&game, &small, Sampling::WithReplacement).unwrap();
+        let large = infer(&game, &large, Sampling::WithReplacement).unwrap();
+
+        assert!(small.mean(&Colour::RED).unwrap() < large.mean(&Colour::RED).unwrap())
+    }
+
+    #[test]
+    fn credible_interval_widens_with_level() {
+        let game = game("Game 1: 2 red, 1 blue; 1 red, 2 blue");
+        let posterior = infer(
+            &game,
+            &SizePrior::uniform(3..=30),
+            Sampling::WithReplacement,
+        )
+        .unwrap();
+
+        let (narrow_low, narrow_high) = posterior.credible_interval(&Colour::RED, 0.5).unwrap();
+        let (wide_low, wide_high) = posterior.credible_interval(&Colour::RED, 0.99).unwrap();
+
+        assert!(wide_low <= narrow_low && narrow_high <= wide_high);
+        assert_eq!(
+            (
+                1,
+                posterior.marginal(&Colour::RED).unwrap().len() as u32 - 1
+            ),
+            posterior.credible_interval(&Colour::RED, 1.0).unwrap()
+        )
+    }
+
+    #[test]
+    fn game_without_draws_has_no_posterior() {
+        let game = Game {
+            id: 1,
+            rounds: vec![],
+        };
+
+        assert!(infer(&game, &SizePrior::uniform(1..=5), Sampling::WithReplacement).is_err())
+    }
+
+    #[test]
+    fn colours_the_game_never_showed_have_no_summary() {
+        let game = game("Game 1: 2 red");
+        let posterior =
+            infer(&game, &SizePrior::uniform(2..=4), Sampling::WithReplacement).unwrap();
+
+        assert_eq!(None, posterior.marginal(&Colour::BLUE));
+        assert_eq!(None, posterior.mean(&Colour::BLUE));
+        assert_eq!(None, posterior.credible_interval(&Colour::BLUE, 0.9))
+    }
+
+    #[test]
+    fn priors_with_too_many_bags_are_refused() {
+        let game = game("Game 1: 1 red, 1 green, 1 blue");
+        let wide = GameParser::default()
+            .with_unknown_colours(UnknownColours::Include)
+            .parse("Game 2: 1 red, 1 green, 1 blue, 1 cyan, 1 magenta, 1 yellow")
+            .unwrap();
+
+        assert!(infer(
+            &game,
+            &SizePrior::uniform(0..=100),
+            Sampling::WithReplacement
+        )
+        .is_ok());
+        assert!(matches!(
+            infer(
+                &wide,
+                &SizePrior::uniform(0..=100),
+                Sampling::WithReplacement
+            ),
+            Err(AocError::Inference(_))
+        ))
+    }
+
+    #[test]
+    fn huge_handfuls_do_not_overflow() {
+        let game = game("Game 1: 4000000000 red, 4000000000 blue");
+
+        assert!(infer(
+            &game,
+            &SizePrior::uniform(2..=10),
+            Sampling::WithoutReplacement
+        )
+        .is_err());
+        assert!(infer(
+            &game,
+            &SizePrior::uniform(2..=10),
+            Sampling::WithReplacement
+        )
+        .is_ok())
+    }
+}
@@ -0,0 +1,261 @@
+use std::io::{self, BufRead};
+use std::iter::Map;
+
+use crate::error::AocError;
+
+const BOM: char = '\u{feff}';
+
+pub type ReaderLines<R> = Map<io::Lines<R>, fn(io::Result<String>) -> Result<String, AocError>>;
+
+pub type StrLines<I, S> = Map<I, fn(S) -> Result<String, AocError>>;
+
+pub fn normalise_line(line: &str) -> &str {
+    line.trim_end_matches(['\r', '\n'])
+}
+
+// Normalises raw lines as they stream past: strips a leading BOM and line
+// endings, drops trailing blank lines and optionally checks that every line
+// has the same width. The first error stops iteration and is reported by
+// `process`.
+pub struct Lines<I> {
+    inner: I,
+    started: bool,
+    line: usize,
+    blanks: usize,
+    pending: Option<String>,
+    rectangular: bool,
+    width: Option<usize>,
+    error: Option<AocError>,
+}
+
+impl<R: BufRead> Lines<ReaderLines<R>> {
+    pub fn from_reader(input: R) -> Self {
+        Self::new(input.lines().map(|line| line.map_err(AocError::from)))
+    }
+}
+
+impl<J, S> Lines<StrLines<J, S>>
+where
+    J: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    pub fn from_lines<I>(lines: I) -> Self
+    where
+        I: IntoIterator<IntoIter = J, Item = S>,
+    {
+        Self::new(lines.into_iter().map(|line| Ok(line.as_ref().to_string())))
+    }
+}
+
+impl<I> Lines<I>
+where
+    I: Iterator<Item = Result<String, AocError>>,
+{
+    fn new(inner: I) -> Self {
+        Self {
+            inner,
+            started: false,
+            line: 0,
+            blanks: 0,
+            pending: None,
+            rectangular: false,
+            width: None,
+            error: None,
+        }
+    }
+
+    pub fn rectangular(mut self) -> Self {
+        self.rectangular = true;
+        self
+    }
+
+    pub fn process<T, F>(mut self, f: F) -> Result<T, AocError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, AocError>,
+    {
+        let result = f(&mut self);
+        match self.error {
+            Some(e) => Err(e),
+            None => result,
+        }
+    }
+
+    fn next_raw(&mut self) -> Option<String> {
+        match self.inner.next()? {
+            Ok(line) => {
+                let mut line = normalise_line(&line).to_string();
+                if !self.started && line.starts_with(BOM) {
+                    line.remove(0);
+                }
+                self.started = true;
+                Some(line)
+            }
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+
+    fn check_width(&mut self, line: &str) -> bool {
+        if !self.rectangular {
+            return true;
+        }
+
+        let width = line.chars().count();
+        match self.width {
+            None => {
+                self.width = Some(width);
+                true
+            }
+            Some(expected) if expected == width => true,
+            Some(expected) => {
+                let message = format!("expected {} columns, found {}", expected, width);
+                let error = AocError::parse(expected.min(width) + 1, message).at_line(self.line);
+                self.error = Some(error);
+                false
+            }
+        }
+    }
+}
+
+impl<I> Iterator for Lines<I>
+where
+    I: Iterator<Item = Result<String, AocError>>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        // Blank lines are held back until a non-blank line shows they are
+        // not trailing.
+        let line = if self.blanks > 0 && self.pending.is_some() {
+            self.blanks -= 1;
+            String::new()
+        } else if let Some(line) = self.pending.take() {
+            line
+        } else {
+            loop {
+                let line = self.next_raw()?;
+                if line.trim().is_empty() {
+                    self.blanks += 1;
+                    continue;
+                }
+                if self.blanks > 0 {
+                    self.blanks -= 1;
+                    self.pending = Some(line);
+                    break String::new();
+                }
+                break line;
+            }
+        };
+
+        self.line += 1;
+        if self.check_width(&line) {
+            Some(line)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod input_tests {
+    use super::*;
+
+    struct FailingReader {
+        served: bool,
+    }
+
+    impl io::Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.served {
+                return Err(io::Error::other("disk on fire"));
+            }
+            self.served = true;
+            let line = b"first\n";
+            buf[..line.len()].copy_from_slice(line);
+            Ok(line.len())
+        }
+    }
+
+    fn collect(input: &str) -> Vec<String> {
+        Lines::from_reader(input.as_bytes())
+            .process(|lines| Ok(lines.collect()))
+            .unwrap()
+    }
+
+    macro_rules! normalise_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (input, expected): (&str, Vec<&str>) = $value;
+                    assert_eq!(expected, collect(input))
+                }
+            )*
+        }
+    }
+
+    normalise_tests! {
+        lf_lines_are_unchanged: ("a\nbb\nccc", vec!["a", "bb", "ccc"]),
+        crlf_lines_are_stripped: ("a\r\nbb\r\nccc\r\n", vec!["a", "bb", "ccc"]),
+        bom_is_stripped: ("\u{feff}a\nb", vec!["a", "b"]),
+        trailing_blank_lines_are_dropped: ("a\nb\n\n\r\n  \n", vec!["a", "b"]),
+        interior_blank_lines_are_kept: ("a\n\n\nb\n\n", vec!["a", "", "", "b"]),
+        leading_blank_lines_are_kept: ("\na", vec!["", "a"]),
+        empty_input_has_no_lines: ("", vec![]),
+    }
+
+    #[test]
+    fn from_lines_strips_embedded_line_endings() {
+        let input = vec![String::from("..12\r\n"), String::from("*...\r\n")];
+
+        let lines: Vec<String> = Lines::from_lines(input).collect();
+
+        assert_eq!(vec!["..12", "*..."], lines)
+    }
+
+    #[test]
+    fn process_streams_every_line() {
+        let input = "a\nbb\nccc".as_bytes();
+
+        let lengths = Lines::from_reader(input)
+            .process(|lines| Ok(lines.map(|l| l.len()).collect::<Vec<_>>()));
+
+        assert_eq!(vec![1, 2, 3], lengths.unwrap())
+    }
+
+    #[test]
+    fn process_reports_io_error_after_partial_read() {
+        let input = io::BufReader::new(FailingReader { served: false });
+
+        let result = Lines::from_reader(input).process(|lines| Ok(lines.count()));
+
+        assert!(matches!(result, Err(AocError::Io(_))))
+    }
+
+    #[test]
+    fn rectangular_accepts_equal_widths() {
+        let result = Lines::from_lines(["..#", "#..", "\u{b7}.."])
+            .rectangular()
+            .process(|lines| Ok(lines.count()));
+
+        assert_eq!(3, result.unwrap())
+    }
+
+    #[test]
+    fn rectangular_reports_ragged_line() {
+        let result = Lines::from_lines(["....", "....", "..."])
+            .rectangular()
+            .process(|lines| Ok(lines.count()));
+
+        assert_eq!(
+            "line 3, column 4: expected 4 columns, found 3",
+            result.unwrap_err().to_string()
+        )
+    }
+}
@@ -1,86 +1,256 @@
-use std::collections::HashMap;
-
-use crate::card::{win_pow, Card};
-use crate::game::CubesPulled;
-
+use std::io::BufRead;
+
+use crate::days::{Day1, Day2, Day3, Day4};
+use crate::input::Lines;
+
+pub use crate::answer::Answer;
+pub use crate::automaton::{Automaton, Match, Matches};
+pub use crate::bench::{bench_entry, BenchResult, BenchRun, History, Phase, Regression, Stats};
+pub use crate::calibration_generator::CalibrationGenerator;
+pub use crate::client::{Client, InputCache, DEFAULT_BASE_URL, YEAR};
+pub use crate::days::BagReport;
+pub use crate::digit_scan::{
+    calibration_digit_sum, first_calibration_digit, first_calibration_digit_scalar,
+    last_calibration_digit, last_calibration_digit_scalar,
+};
+pub use crate::document::Document;
+pub use crate::error::AocError;
+pub use crate::explain::{CalibrationReport, Explanation, Highlight};
+pub use crate::extractor::{DigitExtractor, NaiveExtractor, RegexExtractor, ReverseScanExtractor};
+pub use crate::game::{Bag, Colour, Draw, Game, GameParser, Round, Shortfall, UnknownColours};
+pub use crate::game_scan::{scan_game, GameTally};
+pub use crate::game_simulator::{GameSimulator, RoundCounts};
+pub use crate::inference::{infer, Posterior, Sampling, SizePrior};
+pub use crate::manifest::{ExpectedAnswer, Manifest, Outcome, Verification};
+pub use crate::number_phrases::{combine_line, find_numbers, Combine, NumberPhrase};
+pub use crate::solver::{Entry, Registry, Solver};
+pub use crate::string_utils::{decimal_value, DigitSet};
+pub use crate::submit::{submit, Attempt, AttemptLog, Refusal, Verdict};
+pub use crate::vocabulary::{DigitMatch, DigitVocabulary};
+
+mod answer;
+mod automaton;
+mod bench;
+mod calibration_generator;
 mod card;
+mod client;
+mod clock;
+mod days;
+mod digit_scan;
+mod document;
 mod engine;
+mod error;
+mod explain;
+mod extractor;
 mod game;
+mod game_scan;
+mod game_simulator;
+mod inference;
+mod input;
+mod manifest;
+mod number_phrases;
+mod rng;
+mod solver;
 mod string_utils;
+mod submit;
+#[cfg(test)]
+mod test_server;
+mod vocabulary;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    registry.register::<Day1>(1);
+    registry.register::<Day2>(2);
+    registry.register::<Day3>(3);
+    registry.register::<Day4>(4);
+    registry
+}
+
+fn expect_answer<T>(result: Result<T, AocError>) -> Answer
+where
+    T: Into<Answer>,
+{
+    result.map(Into::into).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn solve_day_1_part_1<I, S>(lines: I) -> Answer
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    expect_answer(
+        Lines::from_lines(lines)
+            .process(|lines| Ok(days::calibration_sum(lines, days::digits_only()))),
+    )
+}
+
+pub fn try_solve_day_1_part_1(input: impl BufRead) -> Result<Answer, AocError> {
+    try_solve_day_1_with(input, days::digits_only())
+}
+
+pub fn solve_day_1_part_2<I, S>(lines: I) -> Answer
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    expect_answer(
+        Lines::from_lines(lines).process(|lines| Ok(days::calibration_sum(lines, days::english()))),
+    )
+}
+
+pub fn try_solve_day_1_part_2(input: impl BufRead) -> Result<Answer, AocError> {
+    try_solve_day_1_with(input, days::english())
+}
+
+pub fn try_solve_day_1_with(
+    input: impl BufRead,
+    vocabulary: &DigitVocabulary,
+) -> Result<Answer, AocError> {
+    Lines::from_reader(input)
+        .process(|lines| Ok(days::calibration_sum(lines, vocabulary)))
+        .map(Answer::from)
+}
 
-pub fn solve_day_1_part_1(lines: Vec<String>) -> i32 {
-    lines
-        .iter()
-        .map(|line| string_utils::get_number_from_string(line, false))
-        .sum()
+pub fn try_solve_day_1_strict(
+    input: impl BufRead,
+    vocabulary: &DigitVocabulary,
+) -> Result<Answer, AocError> {
+    Lines::from_reader(input)
+        .process(|lines| days::try_calibration_sum(lines, vocabulary))
+        .map(Answer::from)
 }
 
-pub fn solve_day_1_part_2(lines: Vec<String>) -> i32 {
-    lines
-        .iter()
-        .map(|line| string_utils::get_number_from_string(line, true))
-        .sum()
+pub fn report_day_1(
+    input: impl BufRead,
+    vocabulary: &DigitVocabulary,
+) -> Result<CalibrationReport, AocError> {
+    Lines::from_reader(input).process(|lines| Ok(days::calibration_report(lines, vocabulary)))
 }
 
-pub fn solve_day_2_part_1(lines: Vec<String>) -> i32 {
-    const MAX_RED: i32 = 12;
-    const MAX_GREEN: i32 = 13;
-    const MAX_BLUE: i32 = 14;
-
-    lines
-        .iter()
-        .map(|line| game::map_input_to_cubes_puled(line))
-        .map(|collection| CubesPulled::collect_max(collection))
-        .filter(|item| item.red <= MAX_RED && item.green <= MAX_GREEN && item.blue <= MAX_BLUE)
-        .map(|item| item.id)
-        .sum()
+pub fn explain_day_1(
+    input: impl BufRead,
+    vocabulary: &DigitVocabulary,
+) -> Result<Vec<Explanation>, AocError> {
+    Lines::from_reader(input).process(|lines| {
+        Ok(lines
+            .map(|line| string_utils::explain_line(&line, vocabulary))
+            .collect())
+    })
 }
 
-pub fn solve_day_2_part_2(lines: Vec<String>) -> i32 {
-    lines
-        .iter()
-        .map(|line| game::map_input_to_cubes_puled(line))
-        .map(|collection| CubesPulled::collect_min(collection))
-        .map(|item| item.pow())
-        .sum()
+// Day 1 extended to whole numbers in free text: every numeral and English
+// number phrase on a line is found and the line is scored by `rule`.
+pub fn try_sum_numbers(input: impl BufRead, rule: Combine) -> Result<Answer, AocError> {
+    Lines::from_reader(input)
+        .process(|lines| days::number_sum(lines, rule))
+        .map(Answer::from)
 }
 
-pub fn solve_day_3_part_1(lines: Vec<String>) -> u32 {
-    engine::process_input(lines).iter().map(|p| p.number).sum()
+pub fn solve_day_2_part_1<I, S>(lines: I) -> Answer
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    expect_answer(Lines::from_lines(lines).process(|lines| days::possible_game_id_sum(lines)))
 }
 
-pub fn solve_day_3_part_2(lines: Vec<String>) -> u32 {
-    let parts = engine::process_input(lines);
+pub fn try_solve_day_2_part_1(input: impl BufRead) -> Result<Answer, AocError> {
+    Lines::from_reader(input)
+        .process(|lines| days::possible_game_id_sum(lines))
+        .map(Answer::from)
+}
+
+pub fn check_day_2_bag(input: impl BufRead, bag: &Bag) -> Result<BagReport, AocError> {
+    Lines::from_reader(input).process(|lines| days::bag_report(lines, bag))
+}
+
+pub fn solve_day_2_part_2<I, S>(lines: I) -> Answer
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    expect_answer(Lines::from_lines(lines).process(|lines| days::power_sum(lines)))
+}
+
+pub fn try_solve_day_2_part_2(input: impl BufRead) -> Result<Answer, AocError> {
+    Lines::from_reader(input)
+        .process(|lines| days::power_sum(lines))
+        .map(Answer::from)
+}
+
+pub fn solve_day_3_part_1<I, S>(lines: I) -> Answer
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    expect_answer(
+        Lines::from_lines(lines)
+            .rectangular()
+            .process(|lines| engine::try_process_input(lines))
+            .map(|p| days::part_number_sum(&p)),
+    )
+}
+
+pub fn try_solve_day_3_part_1(input: impl BufRead) -> Result<Answer, AocError> {
+    Lines::from_reader(input)
+        .rectangular()
+        .process(|lines| engine::try_process_input(lines))
+        .map(|p| Answer::from(days::part_number_sum(&p)))
+}
+
+pub fn solve_day_3_part_2<I, S>(lines: I) -> Answer
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    expect_answer(
+        Lines::from_lines(lines)
+            .rectangular()
+            .process(|lines| engine::try_process_input(lines))
+            .and_then(|p| days::gear_ratio_sum(&p)),
+    )
+}
+
+pub fn try_solve_day_3_part_2(input: impl BufRead) -> Result<Answer, AocError> {
+    Lines::from_reader(input)
+        .rectangular()
+        .process(|lines| engine::try_process_input(lines))
+        .and_then(|p| days::gear_ratio_sum(&p))
+        .map(Answer::from)
+}
 
-    engine::collect(parts)
-        .iter()
-        .filter(|(_, v)| v.len() == 2)
-        .map(|(_, v)| v[0].number * v[1].number)
-        .sum()
+pub fn solve_day_4_part_1<I, S>(lines: I) -> Answer
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    expect_answer(Lines::from_lines(lines).process(|lines| days::points(lines)))
 }
 
-pub fn solve_day_4_part_1(lines: Vec<String>) -> u32 {
-    lines
-        .iter()
-        .map(|c| Card::from_string(c))
-        .map(|c| c.get_wins())
-        .map(|w| win_pow(w))
-        .sum()
+pub fn try_solve_day_4_part_1(input: impl BufRead) -> Result<Answer, AocError> {
+    Lines::from_reader(input)
+        .process(|lines| days::points(lines))
+        .map(Answer::from)
 }
 
-pub fn solve_day_4_part_2(lines: Vec<String>) -> u32 {
-    let map: HashMap<u32, Card> = lines
-        .iter()
-        .map(|c| Card::from_string(c))
-        .map(|c| (c.id, c))
-        .collect();
+pub fn solve_day_4_part_2<I, S>(lines: I) -> Answer
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    expect_answer(Lines::from_lines(lines).process(|lines| days::card_count(lines)))
+}
 
-    let wins = Card::generate_from_wins(map);
-    wins.len().try_into().expect("Should have value")
+pub fn try_solve_day_4_part_2(input: impl BufRead) -> Result<Answer, AocError> {
+    Lines::from_reader(input)
+        .process(|lines| days::card_count(lines))
+        .map(Answer::from)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
     use super::*;
 
     #[test]
@@ -91,7 +261,7 @@ mod tests {
             String::from("a1b2c3d4e5f"),
             String::from("treb7uchet"),
         ];
-        let expected_result = 142;
+        let expected_result = Answer::from(142);
 
         let result = solve_day_1_part_1(input);
         assert_eq!(expected_result, result)
@@ -108,7 +278,7 @@ mod tests {
             String::from("zoneight234"),
             String::from("7pqrstsixteen"),
         ];
-        let expected_result = 281;
+        let expected_result = Answer::from(281);
 
         let result = solve_day_1_part_2(input);
         assert_eq!(expected_result, result)
@@ -128,7 +298,19 @@ mod tests {
             String::from("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"),
         ];
 
-        let expected_result = 8;
+        let expected_result = Answer::from(8);
+        let result = solve_day_2_part_1(input);
+        assert_eq!(expected_result, result)
+    }
+
+    #[test]
+    fn solve_day_2_part_1_with_large_ids_does_not_overflow() {
+        let input = vec![
+            String::from("Game 4294967295: 1 red"),
+            String::from("Game 4294967294: 2 blue"),
+        ];
+
+        let expected_result = Answer::from(8589934589u64);
         let result = solve_day_2_part_1(input);
         assert_eq!(expected_result, result)
     }
@@ -147,7 +329,7 @@ mod tests {
             String::from("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"),
         ];
 
-        let expected_result = 2286;
+        let expected_result = Answer::from(2286);
         let result = solve_day_2_part_2(input);
         assert_eq!(expected_result, result)
     }
@@ -167,7 +349,7 @@ mod tests {
             String::from(".664.598.."),
         ];
 
-        let expected_result = 4361;
+        let expected_result = Answer::from(4361);
         let result = solve_day_3_part_1(input);
         assert_eq!(expected_result, result)
     }
@@ -187,7 +369,7 @@ mod tests {
             String::from(".664.598.."),
         ];
 
-        let expected_result = 467835;
+        let expected_result = Answer::from(467835);
         let result = solve_day_3_part_2(input);
 
         assert_eq!(expected_result, result)
@@ -204,7 +386,7 @@ mod tests {
             String::from("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"),
         ];
 
-        let expected_result = 13;
+        let expected_result = Answer::from(13);
         let result = solve_day_4_part_1(input);
 
         assert_eq!(expected_result, result)
@@ -221,105 +403,255 @@ mod tests {
             String::from("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"),
         ];
 
-        let expected_result = 30;
+        let expected_result = Answer::from(30);
         let result = solve_day_4_part_2(input);
 
         assert_eq!(expected_result, result)
     }
 
     #[test]
-    fn solve_day_1_part_1_with_challenge_input() {
-        let lines: Vec<String> = include_str!("../inputs/day_1.txt")
-            .split_whitespace()
-            .map(|s| s.to_string())
-            .collect();
+    fn registry_contains_every_implemented_day() {
+        let registry = registry();
+
+        assert_eq!(vec![1, 2, 3, 4], registry.days());
+        for day in 1..=4 {
+            assert!(registry.get(day, 1).is_some());
+            assert!(registry.get(day, 2).is_some());
+        }
+    }
 
-        let expected_result = 56397;
-        let result = solve_day_1_part_1(lines);
-        assert_eq!(expected_result, result)
+    #[test]
+    fn registry_entry_matches_solve_function() {
+        let input = [
+            String::from("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"),
+            String::from("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"),
+            String::from("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"),
+            String::from("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83"),
+            String::from("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36"),
+            String::from("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"),
+        ];
+
+        let registry = registry();
+        let solver = registry
+            .get(4, 1)
+            .unwrap()
+            .parse(&mut input.join("\n").as_bytes())
+            .unwrap();
+
+        assert_eq!(Answer::from(13), solver.part1());
+        assert_eq!(Answer::from(30), solver.part2())
     }
 
     #[test]
-    fn solve_day_1_part_2_with_challenge_input() {
-        let lines: Vec<String> = include_str!("../inputs/day_1.txt")
-            .split_whitespace()
-            .map(|s| s.to_string())
-            .collect();
+    fn try_solve_day_2_part_1_with_malformed_line_reports_line() {
+        let input = [
+            String::from("Game 1: 3 blue, 4 red"),
+            String::from("Game 2 1 blue"),
+        ];
 
-        let expected_result = 55701;
-        let result = solve_day_1_part_2(lines);
-        assert_eq!(expected_result, result)
+        let result = try_solve_day_2_part_1(input.join("\n").as_bytes());
+
+        match result {
+            Err(AocError::Parse { line, .. }) => assert_eq!(2, line),
+            other => panic!("expected parse error, got {:?}", other),
+        }
     }
 
     #[test]
-    fn solve_day_2_part_1_with_challenge_input() {
-        let lines: Vec<String> = include_str!("../inputs/day_2.txt")
-            .lines()
-            .map(|s| s.to_string())
-            .collect();
+    fn try_solve_day_4_part_1_with_malformed_line_reports_line_and_column() {
+        let input = [
+            String::from("Card 1: 41 48 | 83 86"),
+            String::from("Card 2: 13 32 | 61 3O"),
+        ];
 
-        let expected_result = 2563;
-        let result = solve_day_2_part_1(lines);
-        assert_eq!(expected_result, result)
+        let result = try_solve_day_4_part_1(input.join("\n").as_bytes());
+
+        assert_eq!(
+            "line 2, column 20: invalid number '3O'",
+            result.unwrap_err().to_string()
+        )
     }
 
     #[test]
-    fn solve_day_2_part_2_with_challenge_input() {
-        let lines: Vec<String> = include_str!("../inputs/day_2.txt")
-            .lines()
-            .map(|s| s.to_string())
-            .collect();
+    fn solve_day_1_part_1_with_streamed_lines() {
+        let lines = std::iter::repeat_n("1abc2", 1_000);
 
-        let expected_result = 70768;
-        let result = solve_day_2_part_2(lines);
-        assert_eq!(expected_result, result)
+        assert_eq!(Answer::from(12_000), solve_day_1_part_1(lines))
     }
 
     #[test]
-    fn solve_day_3_part_1_with_challenge_input() {
-        let lines: Vec<String> = include_str!("../inputs/day_3.txt")
-            .lines()
-            .map(|s| s.to_string())
-            .collect();
+    fn try_solve_day_3_part_1_with_reader_input() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..\n";
 
-        let expected_result = 539590;
-        let result = solve_day_3_part_1(lines);
-        assert_eq!(expected_result, result)
+        let result = try_solve_day_3_part_1(input.as_bytes());
+
+        assert_eq!(Answer::from(4361), result.unwrap())
     }
 
     #[test]
-    fn solve_day_3_part_2_with_challenge_input() {
-        let lines: Vec<String> = include_str!("../inputs/day_3.txt")
-            .lines()
-            .map(|s| s.to_string())
-            .collect();
+    fn try_solve_day_3_part_2_with_large_gear_ratio() {
+        let result = try_solve_day_3_part_2("99999*99999\n".as_bytes());
 
-        let expected_result = 80703636;
-        let result = solve_day_3_part_2(lines);
-        assert_eq!(expected_result, result)
+        assert_eq!(Answer::from(9999800001u64), result.unwrap())
     }
 
     #[test]
-    fn solve_day_4_part_1_with_challenge_input() {
-        let lines: Vec<String> = include_str!("../inputs/day_4.txt")
-            .lines()
-            .map(|s| s.to_string())
-            .collect();
+    fn try_solve_day_3_part_2_with_overflowing_total_is_error() {
+        let input = "4294967295*4294967295\n.....................\n4294967295*4294967295\n";
 
-        let expected_result = 21213;
-        let result = solve_day_4_part_1(lines);
-        assert_eq!(expected_result, result)
+        let result = try_solve_day_3_part_2(input.as_bytes());
+
+        match result {
+            Err(AocError::Overflow(message)) => {
+                assert_eq!("the total of all gear ratios", message)
+            }
+            other => panic!("expected overflow error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn try_solve_day_1_strict_reports_first_line_without_digit() {
+        let input = "1abc2\npqr3stu8vwx\nabcdef\n\ntreb7uchet\n";
+
+        let lenient = try_solve_day_1_with(input.as_bytes(), days::english());
+        let strict = try_solve_day_1_strict(input.as_bytes(), days::english());
+
+        assert_eq!(Answer::from(12 + 38 + 77), lenient.unwrap());
+        assert_eq!("line 3: no digit found", strict.unwrap_err().to_string())
+    }
+
+    #[test]
+    fn report_day_1_lists_every_line_without_digit() {
+        let input = "1abc2\npqr3stu8vwx\nabcdef\n\ntreb7uchet\n";
+
+        let report = report_day_1(input.as_bytes(), days::english()).unwrap();
+
+        assert_eq!(12 + 38 + 77, report.total);
+        assert_eq!(vec![3, 4], report.missing)
+    }
+
+    #[test]
+    fn challenge_input_for_day_1_is_clean() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let input = std::fs::read(root.join("inputs/day_1.txt")).unwrap();
+
+        let result = try_solve_day_1_strict(&input[..], days::english());
+
+        assert_eq!(Answer::from(55701), result.unwrap())
+    }
+
+    #[test]
+    fn explain_day_1_agrees_with_solver() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let input = std::fs::read(root.join("inputs/day_1.txt")).unwrap();
+
+        let explanations = explain_day_1(&input[..], days::english()).unwrap();
+        let total: u64 = explanations.iter().map(|e| e.value).sum();
+
+        assert_eq!(
+            Answer::from(total),
+            solve_day_1_part_2(String::from_utf8(input).unwrap().lines())
+        )
+    }
+
+    #[test]
+    fn try_solve_day_1_with_german_vocabulary() {
+        let input = "Zeile Eins hat 7\nfünfundZWANZIG, dann drei\n";
+        let vocabulary = DigitVocabulary::german().case_insensitive();
+
+        let result = try_solve_day_1_with(input.as_bytes(), &vocabulary);
+
+        assert_eq!(Answer::from(17 + 53), result.unwrap())
+    }
+
+    #[test]
+    fn try_solve_day_1_with_unicode_digits() {
+        let input = "caf\u{e9}\u{ff14}two\n\u{661}\u{1f384}nine\n3x\u{967}\n";
+
+        let ascii = try_solve_day_1_part_2(input.as_bytes()).unwrap();
+        let vocabulary = DigitVocabulary::english().with_digits(DigitSet::Unicode);
+        let unicode = try_solve_day_1_with(input.as_bytes(), &vocabulary).unwrap();
+
+        assert_eq!(Answer::from(22 + 99 + 33), ascii);
+        assert_eq!(Answer::from(42 + 19 + 31), unicode)
+    }
+
+    #[test]
+    fn check_day_2_bag_with_puzzle_bag_matches_part_1() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+        let bag: Bag = "12 red, 13 green, 14 blue".parse().unwrap();
+
+        let report = check_day_2_bag(input.as_bytes(), &bag).unwrap();
+
+        assert_eq!(vec![1, 5], report.possible);
+        assert_eq!(6, report.id_sum());
+        assert_eq!(1, report.impossible.len());
+        assert_eq!(3, report.impossible[0].0);
+        assert_eq!(8, report.impossible[0].1[0].excess())
     }
 
     #[test]
-    fn solve_day_4_part_2_with_challenge_input() {
-        let lines: Vec<String> = include_str!("../inputs/day_4.txt")
-            .lines()
-            .map(|s| s.to_string())
+    fn check_day_2_bag_sums_large_ids_without_overflow() {
+        let input = "Game 4294967295: 1 red\nGame 4294967294: 2 blue\n";
+        let bag: Bag = "12 red, 13 green, 14 blue".parse().unwrap();
+
+        let report = check_day_2_bag(input.as_bytes(), &bag).unwrap();
+
+        assert_eq!(8589934589, report.id_sum())
+    }
+
+    #[test]
+    fn try_sum_numbers_scores_each_line_by_rule() {
+        let input = "took 250ms, retried twenty-one times\nno numbers\nfive of 7\n";
+
+        let first_last = try_sum_numbers(input.as_bytes(), Combine::FirstLast).unwrap();
+        let max = try_sum_numbers(input.as_bytes(), Combine::Max).unwrap();
+
+        assert_eq!(Answer::from(25021u64 + 57), first_last);
+        assert_eq!(Answer::from(250u64 + 7), max)
+    }
+
+    #[test]
+    fn try_sum_numbers_reports_line_that_overflows_the_total() {
+        let input = "1\n18446744073709551615\n2\n";
+
+        match try_sum_numbers(input.as_bytes(), Combine::Sum) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((2, 1), (line, column)),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn try_sum_numbers_reports_line_of_oversized_number() {
+        let input = "1\n2 and 99999999999999999999\n";
+
+        match try_sum_numbers(input.as_bytes(), Combine::Sum) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((2, 7), (line, column)),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn challenge_inputs_match_answers_manifest() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml");
+        let manifest = Manifest::load(&path).unwrap();
+
+        let failures: Vec<String> = manifest
+            .verify(&registry())
+            .iter()
+            .filter(|v| !v.passed())
+            .map(|v| {
+                format!(
+                    "day {} part {}: {:?}",
+                    v.answer.day, v.answer.part, v.outcome
+                )
+            })
             .collect();
 
-        let expected_result = 8549735;
-        let result = solve_day_4_part_2(lines);
-        assert_eq!(expected_result, result)
+        assert!(!manifest.answers.is_empty());
+        assert_eq!(Vec::<String>::new(), failures)
     }
 }
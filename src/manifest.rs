@@ -0,0 +1,197 @@
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solver::Registry;
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum ExpectedValue {
+    Integer(i64),
+    Text(String),
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct ExpectedAnswer {
+    pub day: u8,
+    pub part: u8,
+    pub input: PathBuf,
+    expected: ExpectedValue,
+}
+
+impl ExpectedAnswer {
+    pub fn expected(&self) -> String {
+        match &self.expected {
+            ExpectedValue::Integer(n) => n.to_string(),
+            ExpectedValue::Text(s) => s.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct Manifest {
+    #[serde(rename = "answer", default)]
+    pub answers: Vec<ExpectedAnswer>,
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Correct(Answer),
+    Wrong(Answer),
+    NotImplemented,
+    Failed(AocError),
+}
+
+#[derive(Debug)]
+pub struct Verification<'a> {
+    pub answer: &'a ExpectedAnswer,
+    pub outcome: Outcome,
+}
+
+impl Verification<'_> {
+    pub fn passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Correct(_))
+    }
+}
+
+impl Manifest {
+    pub fn parse(text: &str, root: &Path) -> Result<Self, AocError> {
+        let mut manifest: Manifest =
+            toml::from_str(text).map_err(|e| AocError::Manifest(e.to_string()))?;
+        manifest.root = root.to_path_buf();
+        Ok(manifest)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        let text = fs::read_to_string(path)?;
+        let root = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&text, root)
+    }
+
+    pub fn input_path(&self, answer: &ExpectedAnswer) -> PathBuf {
+        self.root.join(&answer.input)
+    }
+
+    fn check(&self, registry: &Registry, answer: &ExpectedAnswer) -> Outcome {
+        let Some(entry) = registry.get(answer.day, answer.part) else {
+            return Outcome::NotImplemented;
+        };
+
+        let result = File::open(self.input_path(answer))
+            .map_err(AocError::from)
+            .and_then(|file| entry.solve(&mut BufReader::new(file)));
+
+        match result {
+            Ok(actual) if actual.matches(&answer.expected()) => Outcome::Correct(actual),
+            Ok(actual) => Outcome::Wrong(actual),
+            Err(e) => Outcome::Failed(e),
+        }
+    }
+
+    pub fn verify<'a>(&'a self, registry: &Registry) -> Vec<Verification<'a>> {
+        self.answers
+            .iter()
+            .map(|answer| Verification {
+                answer,
+                outcome: self.check(registry, answer),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod manifest_tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn parse_reads_integer_and_text_answers() {
+        let text = r#"
+            [[answer]]
+            day = 1
+            part = 2
+            input = "inputs/day_1.txt"
+            expected = 55701
+
+            [[answer]]
+            day = 25
+            part = 1
+            input = "other/day_25.txt"
+            expected = "340282366920938463463374607431768211455"
+        "#;
+
+        let manifest = Manifest::parse(text, Path::new("root")).unwrap();
+
+        assert_eq!(2, manifest.answers.len());
+        assert_eq!("55701", manifest.answers[0].expected());
+        assert_eq!(
+            "340282366920938463463374607431768211455",
+            manifest.answers[1].expected()
+        );
+        assert_eq!(
+            Path::new("root/other/day_25.txt"),
+            manifest.input_path(&manifest.answers[1])
+        )
+    }
+
+    #[test]
+    fn parse_with_missing_field_is_error() {
+        let text = r#"
+            [[answer]]
+            day = 1
+            input = "inputs/day_1.txt"
+            expected = 1
+        "#;
+
+        let result = Manifest::parse(text, Path::new(""));
+
+        assert!(matches!(result, Err(AocError::Manifest(_))))
+    }
+
+    #[test]
+    fn verify_reports_each_outcome() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let text = r#"
+            [[answer]]
+            day = 2
+            part = 1
+            input = "inputs/day_2.txt"
+            expected = 2563
+
+            [[answer]]
+            day = 2
+            part = 2
+            input = "inputs/day_2.txt"
+            expected = 1
+
+            [[answer]]
+            day = 24
+            part = 1
+            input = "inputs/day_24.txt"
+            expected = 1
+
+            [[answer]]
+            day = 3
+            part = 1
+            input = "inputs/missing.txt"
+            expected = 1
+        "#;
+
+        let manifest = Manifest::parse(text, root).unwrap();
+        let results = manifest.verify(&registry());
+
+        assert!(matches!(results[0].outcome, Outcome::Correct(_)));
+        assert!(matches!(results[1].outcome, Outcome::Wrong(_)));
+        assert!(matches!(results[2].outcome, Outcome::NotImplemented));
+        assert!(matches!(
+            results[3].outcome,
+            Outcome::Failed(AocError::Io(_))
+        ))
+    }
+}
@@ -0,0 +1,393 @@
+use crate::error::AocError;
+
+// Whole numbers in free text, written either as numerals ("1024") or as
+// English phrases ("one hundred and five", "twenty-one"). Unlike day 1
+// digits, words only count when they stand alone, so "someone" has no
+// number in it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberPhrase {
+    pub start: usize,
+    pub end: usize,
+    pub value: u64,
+}
+
+impl NumberPhrase {
+    pub fn text<'a>(&self, line: &'a str) -> &'a str {
+        &line[self.start..self.end]
+    }
+}
+
+// How the numbers found on one line become that line's value. A line with
+// no numbers is worth 0 under every rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Combine {
+    // The decimal digits of the first number followed by those of the last,
+    // the day 1 rule generalised: "12 then 7" is 127 and a lone 42 is 4242.
+    #[default]
+    FirstLast,
+    Sum,
+    Max,
+}
+
+impl Combine {
+    // None when the combined value does not fit in a u64.
+    pub fn apply(&self, values: &[u64]) -> Option<u64> {
+        let (Some(&first), Some(&last)) = (values.first(), values.last()) else {
+            return Some(0);
+        };
+
+        match self {
+            Combine::FirstLast => {
+                let shift = 10u64.checked_pow(last.checked_ilog10().unwrap_or(0) + 1)?;
+                first.checked_mul(shift)?.checked_add(last)
+            }
+            Combine::Sum => values.iter().try_fold(0u64, |sum, v| sum.checked_add(*v)),
+            Combine::Max => values.iter().copied().max(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Word {
+    Unit(u64),
+    Teen(u64),
+    Tens(u64),
+    Hundred,
+    Scale(u64),
+    And,
+}
+
+const WORDS: [(&str, Word); 33] = [
+    ("zero", Word::Unit(0)),
+    ("one", Word::Unit(1)),
+    ("two", Word::Unit(2)),
+    ("three", Word::Unit(3)),
+    ("four", Word::Unit(4)),
+    ("five", Word::Unit(5)),
+    ("six", Word::Unit(6)),
+    ("seven", Word::Unit(7)),
+    ("eight", Word::Unit(8)),
+    ("nine", Word::Unit(9)),
+    ("ten", Word::Teen(10)),
+    ("eleven", Word::Teen(11)),
+    ("twelve", Word::Teen(12)),
+    ("thirteen", Word::Teen(13)),
+    ("fourteen", Word::Teen(14)),
+    ("fifteen", Word::Teen(15)),
+    ("sixteen", Word::Teen(16)),
+    ("seventeen", Word::Teen(17)),
+    ("eighteen", Word::Teen(18)),
+    ("nineteen", Word::Teen(19)),
+    ("twenty", Word::Tens(20)),
+    ("thirty", Word::Tens(30)),
+    ("forty", Word::Tens(40)),
+    ("fifty", Word::Tens(50)),
+    ("sixty", Word::Tens(60)),
+    ("seventy", Word::Tens(70)),
+    ("eighty", Word::Tens(80)),
+    ("ninety", Word::Tens(90)),
+    ("hundred", Word::Hundred),
+    ("thousand", Word::Scale(1_000)),
+    ("million", Word::Scale(1_000_000)),
+    ("billion", Word::Scale(1_000_000_000)),
+    ("and", Word::And),
+];
+
+fn word(text: &str) -> Option<Word> {
+    WORDS
+        .iter()
+        .find(|(w, _)| w.eq_ignore_ascii_case(text))
+        .map(|(_, word)| *word)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Word,
+    Numeral,
+    Space,
+    Hyphen,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    kind: Kind,
+    start: usize,
+    end: usize,
+}
+
+// Letters, digits and spaces form maximal runs; everything else is a token
+// of its own.
+fn tokenize(line: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = vec![];
+
+    for (start, c) in line.char_indices() {
+        let kind = match c {
+            c if c.is_ascii_alphabetic() => Kind::Word,
+            c if c.is_ascii_digit() => Kind::Numeral,
+            ' ' => Kind::Space,
+            '-' => Kind::Hyphen,
+            _ => Kind::Other,
+        };
+        let end = start + c.len_utf8();
+
+        match tokens.last_mut() {
+            Some(last)
+                if last.kind == kind
+                    && matches!(kind, Kind::Word | Kind::Numeral | Kind::Space) =>
+            {
+                last.end = end
+            }
+            _ => tokens.push(Token { kind, start, end }),
+        }
+    }
+
+    tokens
+}
+
+fn too_large(line: &str, start: usize, end: usize) -> AocError {
+    AocError::parse(
+        start + 1,
+        format!("'{}' does not fit in 64 bits", &line[start..end]),
+    )
+}
+
+struct Phrase {
+    total: u64,
+    current: u64,
+    last: Option<Word>,
+    scale: Option<u64>,
+}
+
+impl Phrase {
+    fn accepts(&self, word: Word) -> bool {
+        match (self.last, word) {
+            (None, Word::Hundred | Word::Scale(_) | Word::And) => false,
+            (None, _) => true,
+            // "zero" never starts a longer phrase.
+            (Some(Word::Unit(0)), _) => false,
+            (Some(Word::Tens(_)), Word::Unit(n)) => n > 0,
+            (Some(Word::Hundred | Word::Scale(_) | Word::And), Word::Unit(n)) => n > 0,
+            (Some(Word::Hundred | Word::Scale(_) | Word::And), Word::Teen(_) | Word::Tens(_)) => {
+                true
+            }
+            (Some(Word::Unit(_) | Word::Teen(_) | Word::Tens(_)), Word::Hundred) => {
+                self.current < 100
+            }
+            (
+                Some(Word::Unit(_) | Word::Teen(_) | Word::Tens(_) | Word::Hundred),
+                Word::Scale(s),
+            ) => self.scale.is_none_or(|previous| s < previous),
+            (Some(Word::Hundred | Word::Scale(_)), Word::And) => true,
+            _ => false,
+        }
+    }
+
+    // None on overflow.
+    fn push(&mut self, word: Word) -> Option<()> {
+        match word {
+            Word::Unit(n) | Word::Teen(n) | Word::Tens(n) => self.current += n,
+            Word::Hundred => self.current *= 100,
+            Word::Scale(s) => {
+                self.total = self.total.checked_add(self.current.checked_mul(s)?)?;
+                self.current = 0;
+                self.scale = Some(s);
+            }
+            Word::And => {}
+        }
+        self.last = Some(word);
+        Some(())
+    }
+
+    fn value(&self) -> Option<u64> {
+        self.total.checked_add(self.current)
+    }
+}
+
+// Reads the longest phrase starting at `tokens[i]`, returning it with the
+// index of the first token after it.
+fn phrase(
+    line: &str,
+    tokens: &[Token],
+    i: usize,
+) -> Result<Option<(NumberPhrase, usize)>, AocError> {
+    let mut phrase = Phrase {
+        total: 0,
+        current: 0,
+        last: None,
+        scale: None,
+    };
+    let start = tokens[i].start;
+    let mut committed = None;
+    let mut next = i;
+
+    while let Some(token) = tokens.get(next).filter(|t| t.kind == Kind::Word) {
+        let Some(word) = word(&line[token.start..token.end]).filter(|w| phrase.accepts(*w)) else {
+            break;
+        };
+        phrase
+            .push(word)
+            .ok_or_else(|| too_large(line, start, token.end))?;
+
+        // A trailing "and" is not part of the number.
+        if word != Word::And {
+            let value = phrase
+                .value()
+                .ok_or_else(|| too_large(line, start, token.end))?;
+            committed = Some((
+                NumberPhrase {
+                    start,
+                    end: token.end,
+                    value,
+                },
+                next + 1,
+            ));
+        }
+
+        // Words of one phrase are joined by spaces or a single hyphen.
+        match tokens.get(next + 1).map(|t| t.kind) {
+            Some(Kind::Space | Kind::Hyphen) => next += 2,
+            _ => break,
+        }
+    }
+
+    Ok(committed)
+}
+
+pub fn find_numbers(line: &str) -> Result<Vec<NumberPhrase>, AocError> {
+    let tokens = tokenize(line);
+    let mut numbers = vec![];
+    let mut i = 0;
+
+    while let Some(token) = tokens.get(i) {
+        match token.kind {
+            Kind::Numeral => {
+                let value = line[token.start..token.end]
+                    .parse::<u64>()
+                    .map_err(|_| too_large(line, token.start, token.end))?;
+                numbers.push(NumberPhrase {
+                    start: token.start,
+                    end: token.end,
+                    value,
+                });
+                i += 1;
+            }
+            Kind::Word => match phrase(line, &tokens, i)? {
+                Some((number, next)) => {
+                    numbers.push(number);
+                    i = next;
+                }
+                None => i += 1,
+            },
+            Kind::Space | Kind::Hyphen | Kind::Other => i += 1,
+        }
+    }
+
+    Ok(numbers)
+}
+
+pub fn combine_line(line: &str, rule: Combine) -> Result<u64, AocError> {
+    let numbers = find_numbers(line)?;
+    let values: Vec<u64> = numbers.iter().map(|n| n.value).collect();
+
+    rule.apply(&values).ok_or_else(|| {
+        AocError::parse(
+            numbers[0].start + 1,
+            "the combined value of this line does not fit in 64 bits",
+        )
+    })
+}
+
+#[cfg(test)]
+mod number_phrases_tests {
+    use super::*;
+
+    fn values(line: &str) -> Vec<u64> {
+        find_numbers(line)
+            .unwrap()
+            .iter()
+            .map(|n| n.value)
+            .collect()
+    }
+
+    macro_rules! find_numbers_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (line, expected): (&str, Vec<u64>) = $value;
+                    assert_eq!(expected, values(line))
+                }
+            )*
+        }
+    }
+
+    find_numbers_tests! {
+        single_words: ("one and two", vec![1, 2]),
+        zero_and_teens: ("zero ten nineteen", vec![0, 10, 19]),
+        hyphenated_tens: ("twenty-one", vec![21]),
+        spaced_tens: ("ninety nine bottles", vec![99]),
+        hundreds_with_and: ("one hundred and five", vec![105]),
+        trailing_and_is_dropped: ("one hundred and bananas", vec![100]),
+        thousands: ("two thousand and twenty-three", vec![2023]),
+        millions: ("three million four hundred thousand", vec![3_400_000]),
+        colloquial_hundreds: ("nineteen hundred", vec![1900]),
+        adjacent_units_are_separate: ("one two three", vec![1, 2, 3]),
+        teen_then_tens_is_separate: ("nineteen eighty four", vec![19, 84]),
+        comma_separates_hundreds: ("one hundred, two hundred", vec![100, 200]),
+        zero_stands_alone: ("zero one", vec![0, 1]),
+        multi_digit_numerals: ("error 404 after 1500ms", vec![404, 1500]),
+        numerals_and_words: ("12 then twelve", vec![12, 12]),
+        words_inside_words_are_ignored: ("someone often", vec![]),
+        case_insensitive: ("Twenty-One", vec![21]),
+        punctuation_ends_phrase: ("twenty, one", vec![20, 1]),
+        scale_without_count_is_ignored: ("hundred thousand", vec![]),
+        non_ascii_text_around_numbers: ("café vingt 7 ünd five", vec![7, 5]),
+    }
+
+    macro_rules! combine_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (line, rule, expected) = $value;
+                    assert_eq!(expected, combine_line(line, rule).unwrap())
+                }
+            )*
+        }
+    }
+
+    combine_tests! {
+        first_last_concatenates: ("12 apples and seven pears", Combine::FirstLast, 127),
+        first_last_single_number_repeats: ("forty-two", Combine::FirstLast, 4242),
+        first_last_with_zero: ("10 then zero", Combine::FirstLast, 100),
+        sum_adds_everything: ("one hundred, 20 and three", Combine::Sum, 123),
+        max_takes_largest: ("5 or two thousand or 40", Combine::Max, 2000),
+        empty_line_is_zero: ("no numbers here", Combine::Sum, 0),
+    }
+
+    #[test]
+    fn phrases_carry_byte_spans() {
+        let line = "at twenty-one hundred hours";
+
+        let numbers = find_numbers(line).unwrap();
+
+        assert_eq!(1, numbers.len());
+        assert_eq!("twenty-one hundred", numbers[0].text(line));
+        assert_eq!(2100, numbers[0].value)
+    }
+
+    #[test]
+    fn oversized_numeral_is_an_error() {
+        let error = find_numbers("id 99999999999999999999").unwrap_err();
+
+        assert!(matches!(error, AocError::Parse { column: 4, .. }))
+    }
+
+    #[test]
+    fn oversized_combination_is_an_error() {
+        let error = combine_line("x 18446744073709551615 1", Combine::FirstLast).unwrap_err();
+
+        assert!(matches!(error, AocError::Parse { column: 3, .. }))
+    }
+}
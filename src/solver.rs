@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+
+pub trait Solver {
+    fn try_parse(input: &mut dyn BufRead) -> Result<Self, AocError>
+    where
+        Self: Sized;
+
+    fn parse(input: &mut dyn BufRead) -> Self
+    where
+        Self: Sized,
+    {
+        Self::try_parse(input).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn part1(&self) -> Answer;
+
+    fn part2(&self) -> Answer;
+
+    // Parts whose answer can overflow report it here instead of panicking.
+    fn try_part1(&self) -> Result<Answer, AocError> {
+        Ok(self.part1())
+    }
+
+    fn try_part2(&self) -> Result<Answer, AocError> {
+        Ok(self.part2())
+    }
+}
+
+pub type ParseFn = fn(&mut dyn BufRead) -> Result<Box<dyn Solver>, AocError>;
+
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    parse: ParseFn,
+}
+
+impl Entry {
+    pub fn parse(&self, input: &mut dyn BufRead) -> Result<Box<dyn Solver>, AocError> {
+        (self.parse)(input)
+    }
+
+    pub fn solve_parsed(&self, solver: &dyn Solver) -> Result<Answer, AocError> {
+        match self.part {
+            1 => solver.try_part1(),
+            _ => solver.try_part2(),
+        }
+    }
+
+    pub fn solve(&self, input: &mut dyn BufRead) -> Result<Answer, AocError> {
+        let solver = self.parse(input)?;
+        self.solve_parsed(solver.as_ref())
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    entries: BTreeMap<(u8, u8), Entry>,
+}
+
+fn parse_boxed<S>(input: &mut dyn BufRead) -> Result<Box<dyn Solver>, AocError>
+where
+    S: Solver + 'static,
+{
+    let solver = S::try_parse(input)?;
+    Ok(Box::new(solver))
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<S>(&mut self, day: u8)
+    where
+        S: Solver + 'static,
+    {
+        for part in [1, 2] {
+            let entry = Entry {
+                day,
+                part,
+                parse: parse_boxed::<S>,
+            };
+            self.entries.insert((day, part), entry);
+        }
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Entry> {
+        self.entries.get(&(day, part))
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.values()
+    }
+
+    pub fn days(&self) -> Vec<u8> {
+        let mut days: Vec<u8> = self.entries.keys().map(|(day, _)| *day).collect();
+        days.dedup();
+        days
+    }
+}
+
+#[cfg(test)]
+mod solver_tests {
+    use super::*;
+
+    struct Echo {
+        lines: Vec<String>,
+    }
+
+    impl Solver for Echo {
+        fn try_parse(input: &mut dyn BufRead) -> Result<Self, AocError> {
+            let lines = input.lines().collect::<Result<_, _>>()?;
+            Ok(Self { lines })
+        }
+
+        fn part1(&self) -> Answer {
+            Answer::from(self.lines.len())
+        }
+
+        fn part2(&self) -> Answer {
+            Answer::from(self.lines.iter().map(|l| l.len()).sum::<usize>())
+        }
+    }
+
+    #[test]
+    fn register_adds_both_parts() {
+        let mut registry = Registry::new();
+        registry.register::<Echo>(7);
+
+        assert!(registry.get(7, 1).is_some());
+        assert!(registry.get(7, 2).is_some());
+        assert!(registry.get(7, 3).is_none());
+        assert_eq!(vec![7], registry.days())
+    }
+
+    #[test]
+    fn entry_solve_dispatches_to_part() {
+        let mut registry = Registry::new();
+        registry.register::<Echo>(1);
+
+        let input = "ab\ncde";
+
+        assert_eq!(
+            Answer::from(2),
+            registry
+                .get(1, 1)
+                .unwrap()
+                .solve(&mut input.as_bytes())
+                .unwrap()
+        );
+        assert_eq!(
+            Answer::from(5),
+            registry
+                .get(1, 2)
+                .unwrap()
+                .solve(&mut input.as_bytes())
+                .unwrap()
+        )
+    }
+
+    #[test]
+    fn entries_are_ordered_by_day_then_part() {
+        let mut registry = Registry::new();
+        registry.register::<Echo>(3);
+        registry.register::<Echo>(1);
+
+        let keys: Vec<(u8, u8)> = registry.entries().map(|e| (e.day, e.part)).collect();
+
+        assert_eq!(vec![(1, 1), (1, 2), (3, 1), (3, 2)], keys)
+    }
+}
@@ -1,94 +1,90 @@
-use regex::Regex;
-
-pub fn get_number_from_string(input: &str, capture_none_digits: bool) -> i32 {
-    if capture_none_digits {
-        get_text_or_number_from_string(input)
-    } else {
-        get_digit_only_from_string(input)
-    }
-}
-
-fn get_digit_only_from_string(input: &str) -> i32 {
-    let pattern = r"([1-9])";
-
-    let re = Regex::new(pattern).unwrap();
-    let captures: Vec<i32> = re
-        .find_iter(input)
-        .map(|c| c.as_str())
-        .map(|c| match_str(c))
-        .collect();
-
-    let count = captures.len();
-
-    let (tens, unit) = match count {
-        0 => (0, 0),
-        _ => (*captures.first().unwrap(), *captures.last().unwrap()),
-    };
-
-    (tens * 10) + unit
+use crate::error::AocError;
+use crate::explain::Explanation;
+use crate::extractor::DigitExtractor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DigitSet {
+    #[default]
+    Ascii,
+    Unicode,
 }
 
-fn get_text_or_number_from_string(input: &str) -> i32 {
-    // this is going to be filthy but regex lookbehind fell on its arse
-    let mut captures = vec![];
-
-    for i in 0..input.len() {
-        if let Some(n) = str_contains_number(&input[i..]) {
-            captures.push(n);
+// The zero of every run of ten decimal digits (general category Nd) as of
+// Unicode 15. Each run is contiguous, so a digit's value is its distance
+// from the zero.
+const UNICODE_ZEROS: [u32; 68] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60,
+    0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0,
+    0x1E950, 0x1FBF0,
+];
+
+pub fn decimal_value(c: char, digits: DigitSet) -> Option<u32> {
+    match digits {
+        DigitSet::Ascii => c.to_digit(10),
+        DigitSet::Unicode => {
+            let c = c as u32;
+            let zero = match UNICODE_ZEROS.binary_search(&c) {
+                Ok(i) => UNICODE_ZEROS[i],
+                Err(0) => return None,
+                Err(i) => UNICODE_ZEROS[i - 1],
+            };
+            Some(c - zero).filter(|value| *value < 10)
         }
     }
+}
 
-    let count = captures.len();
-
-    let (tens, unit) = match count {
-        0 => (0, 0),
-        _ => (*captures.first().unwrap(), *captures.last().unwrap()),
-    };
+pub(crate) fn digit_run_zeros(digits: DigitSet) -> &'static [u32] {
+    match digits {
+        DigitSet::Ascii => &UNICODE_ZEROS[..1],
+        DigitSet::Unicode => &UNICODE_ZEROS,
+    }
+}
 
-    (tens * 10) + unit
+pub fn explain_line<E>(input: &str, extractor: &E) -> Explanation
+where
+    E: DigitExtractor + ?Sized,
+{
+    Explanation::new(
+        input,
+        extractor.first_match(input),
+        extractor.last_match(input),
+    )
 }
 
-fn str_contains_number(str: &str) -> Option<i32> {
-    match str {
-        x if x.starts_with("one") => Some(1),
-        x if x.starts_with("two") => Some(2),
-        x if x.starts_with("three") => Some(3),
-        x if x.starts_with("four") => Some(4),
-        x if x.starts_with("five") => Some(5),
-        x if x.starts_with("six") => Some(6),
-        x if x.starts_with("seven") => Some(7),
-        x if x.starts_with("eight") => Some(8),
-        x if x.starts_with("nine") => Some(9),
-        _ => str[0..1].parse::<i32>().ok(),
-    }
+pub fn get_number_from_string<E>(input: &str, extractor: &E) -> u64
+where
+    E: DigitExtractor + ?Sized,
+{
+    extractor.calibration_value(input)
 }
 
-fn match_str(str: &str) -> i32 {
-    match str {
-        "one" => 1,
-        "two" => 2,
-        "three" => 3,
-        "four" => 4,
-        "five" => 5,
-        "six" => 6,
-        "seven" => 7,
-        "eight" => 8,
-        "nine" => 9,
-        _ => str::parse::<i32>(str).unwrap_or(0),
+pub fn try_get_number_from_string<E>(input: &str, extractor: &E) -> Result<u64, AocError>
+where
+    E: DigitExtractor + ?Sized,
+{
+    match (extractor.first_match(input), extractor.last_match(input)) {
+        (Some(first), Some(last)) => Ok((first.value * 10 + last.value) as u64),
+        _ => Err(AocError::NoDigit { line: 1 }),
     }
 }
 
 #[cfg(test)]
 mod string_utils_tests {
     use super::*;
+    use crate::calibration_generator::CalibrationGenerator;
+    use crate::vocabulary::DigitVocabulary;
 
     macro_rules! get_number_from_string_tests {
     ($($name:ident: $value:expr,)*) => {
     $(
         #[test]
         fn $name() {
-            let (input,capture, expected) = $value;
-            let result = get_number_from_string(input, capture);
+            let (input, vocabulary, expected) = $value;
+            let result = get_number_from_string(input, &vocabulary);
             assert_eq!(expected, result)
         }
     )*
@@ -96,32 +92,102 @@ mod string_utils_tests {
 }
 
     get_number_from_string_tests! {
-        line_1_without_capture_nondigit: ("1abc2", false, 12),
-        line_2_without_capture_nondigit: ("pqr3stu8vwx", false, 38),
-        line_3_without_capture_nondigit: ("a1b2c3d4e5f", false,  15),
-        line_4_without_capture_nondigit: ("treb7uchet", false, 77),
-        line_5_without_capture_nondigit: ("two1nine", false, 11),
-        line_6_without_capture_nondigit: ("eightwothree", false, 0),
-        line_7_without_capture_nondigit: ("abcone2threexyz", false, 22),
-        line_8_without_capture_nondigit: ("xtwone3four", false, 33),
-        line_9_without_capture_nondigit: ("4nineeightseven2", false,  42),
-        line_10_without_capture_nondigit: ("zoneight234", false, 24),
-        line_11_without_capture_nondigit: ("7pqrstsixteen", false, 77),
+        line_1_without_capture_nondigit: ("1abc2", DigitVocabulary::digits_only(), 12),
+        line_2_without_capture_nondigit: ("pqr3stu8vwx", DigitVocabulary::digits_only(), 38),
+        line_3_without_capture_nondigit: ("a1b2c3d4e5f", DigitVocabulary::digits_only(),  15),
+        line_4_without_capture_nondigit: ("treb7uchet", DigitVocabulary::digits_only(), 77),
+        line_5_without_capture_nondigit: ("two1nine", DigitVocabulary::digits_only(), 11),
+        line_6_without_capture_nondigit: ("eightwothree", DigitVocabulary::digits_only(), 0),
+        line_7_without_capture_nondigit: ("abcone2threexyz", DigitVocabulary::digits_only(), 22),
+        line_8_without_capture_nondigit: ("xtwone3four", DigitVocabulary::digits_only(), 33),
+        line_9_without_capture_nondigit: ("4nineeightseven2", DigitVocabulary::digits_only(),  42),
+        line_10_without_capture_nondigit: ("zoneight234", DigitVocabulary::digits_only(), 24),
+        line_11_without_capture_nondigit: ("7pqrstsixteen", DigitVocabulary::digits_only(), 77),
     }
 
     get_number_from_string_tests! {
-        line_1_with_capture_nondigit: ("1abc2", true, 12),
-        line_2_with_capture_nondigit: ("pqr3stu8vwx", true, 38),
-        line_3_with_capture_nondigit: ("a1b2c3d4e5f", true,  15),
-        line_4_with_capture_nondigit: ("treb7uchet", true, 77),
-        line_5_with_capture_nondigit: ("two1nine", true, 29),
-        line_6_with_capture_nondigit: ("eightwothree", true, 83),
-        line_7_with_capture_nondigit: ("abcone2threexyz", true, 13),
-        line_8_with_capture_nondigit: ("xtwone3four", true, 24),
-        line_9_with_capture_nondigit: ("4nineeightseven2", true,  42),
-        line_10_with_capture_nondigit: ("zoneight234", true, 14),
-        line_11_with_capture_nondigit: ("7pqrstsixteen", true, 76),
-        line_12_with_capture_nondigit: ("eightoneight", true, 88),
-        line_13_with_capture_nondigit: ("eighthree", true, 83),
+        line_1_with_capture_nondigit: ("1abc2", DigitVocabulary::english(), 12),
+        line_2_with_capture_nondigit: ("pqr3stu8vwx", DigitVocabulary::english(), 38),
+        line_3_with_capture_nondigit: ("a1b2c3d4e5f", DigitVocabulary::english(),  15),
+        line_4_with_capture_nondigit: ("treb7uchet", DigitVocabulary::english(), 77),
+        line_5_with_capture_nondigit: ("two1nine", DigitVocabulary::english(), 29),
+        line_6_with_capture_nondigit: ("eightwothree", DigitVocabulary::english(), 83),
+        line_7_with_capture_nondigit: ("abcone2threexyz", DigitVocabulary::english(), 13),
+        line_8_with_capture_nondigit: ("xtwone3four", DigitVocabulary::english(), 24),
+        line_9_with_capture_nondigit: ("4nineeightseven2", DigitVocabulary::english(),  42),
+        line_10_with_capture_nondigit: ("zoneight234", DigitVocabulary::english(), 14),
+        line_11_with_capture_nondigit: ("7pqrstsixteen", DigitVocabulary::english(), 76),
+        line_12_with_capture_nondigit: ("eightoneight", DigitVocabulary::english(), 88),
+        line_13_with_capture_nondigit: ("eighthree", DigitVocabulary::english(), 83),
+        line_14_with_capture_nondigit: ("sevenine", DigitVocabulary::english(), 79),
+        line_15_with_capture_nondigit: ("oneight", DigitVocabulary::english(), 18),
+        line_16_with_capture_nondigit: ("nothing here", DigitVocabulary::english(), 0),
+    }
+
+    get_number_from_string_tests! {
+        accented_text_is_skipped: ("café1naïve2", DigitVocabulary::digits_only(), 12),
+        emoji_between_words_is_skipped: ("🎄two🎅5", DigitVocabulary::english(), 25),
+        full_width_digits_are_ignored_by_default: ("\u{ff17}a3\u{ff18}", DigitVocabulary::digits_only(), 33),
+        full_width_digits_are_opt_in: ("\u{ff17}a3\u{ff18}", DigitVocabulary::digits_only().with_digits(DigitSet::Unicode), 78),
+        arabic_indic_digits: ("\u{663}abc\u{667}", DigitVocabulary::digits_only().with_digits(DigitSet::Unicode), 37),
+        mixed_scripts_and_words: ("\u{6f1}xtwo\u{969}", DigitVocabulary::english().with_digits(DigitSet::Unicode), 13),
+        devanagari_digits: ("\u{96a}\u{968}", DigitVocabulary::digits_only().with_digits(DigitSet::Unicode), 42),
+        words_win_over_later_unicode_digit: ("nine\u{ff11}", DigitVocabulary::english().with_digits(DigitSet::Unicode), 91),
+        unicode_zero_is_not_a_digit: ("\u{660}\u{ff10}", DigitVocabulary::digits_only().with_digits(DigitSet::Unicode), 0),
+    }
+
+    #[test]
+    fn explanations_agree_with_calibration_values() {
+        let document = CalibrationGenerator::new(14)
+            .with_lines(300)
+            .with_overlap_density(0.5)
+            .generate();
+
+        for vocabulary in [DigitVocabulary::digits_only(), DigitVocabulary::english()] {
+            for line in document.lines.iter() {
+                assert_eq!(
+                    explain_line(line, &vocabulary).value,
+                    get_number_from_string(line, &vocabulary),
+                    "{}",
+                    line
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn try_get_number_from_string_rejects_lines_without_digits() {
+        let vocabulary = DigitVocabulary::english();
+
+        assert_eq!(
+            29,
+            try_get_number_from_string("two1nine", &vocabulary).unwrap()
+        );
+        assert!(matches!(
+            try_get_number_from_string("abcdef", &vocabulary),
+            Err(AocError::NoDigit { line: 1 })
+        ))
+    }
+
+    macro_rules! decimal_value_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (c, digits, expected) = $value;
+                    assert_eq!(expected, decimal_value(c, digits))
+                }
+            )*
+        }
+    }
+
+    decimal_value_tests! {
+        ascii_digit_value: ('7', DigitSet::Ascii, Some(7)),
+        full_width_digit_is_not_ascii: ('\u{ff17}', DigitSet::Ascii, None),
+        full_width_digit_value: ('\u{ff17}', DigitSet::Unicode, Some(7)),
+        mathematical_digit_value: ('\u{1d7d9}', DigitSet::Unicode, Some(1)),
+        letter_before_first_run_is_none: ('a', DigitSet::Unicode, None),
+        letter_past_a_run_is_none: ('\u{66a}', DigitSet::Unicode, None),
+        roman_numeral_is_not_decimal: ('\u{2163}', DigitSet::Unicode, None),
     }
 }
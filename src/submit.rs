@@ -0,0 +1,330 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::client::Client;
+use crate::clock::unix_timestamp;
+use crate::error::AocError;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited { wait: Option<String> },
+    AlreadySolved,
+    Unknown(String),
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = text.split_once(start)?;
+    let (inner, _) = rest.split_once(end)?;
+    Some(inner)
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl Verdict {
+    pub fn from_response(html: &str) -> Self {
+        let article = between(html, "<article>", "</article>").unwrap_or(html);
+        let text = strip_tags(article);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else if text.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if text.contains("You gave an answer too recently") {
+            let wait = between(&text, "You have ", " left to wait").map(String::from);
+            Verdict::RateLimited { wait }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown(text)
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, {} left to wait", wait)
+            }
+            Verdict::RateLimited { wait: None } => write!(f, "rate limited"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::Unknown(text) => write!(f, "unrecognised response: {}", text),
+        }
+    }
+}
+
+pub fn submit(client: &Client, day: u8, part: u8, answer: &Answer) -> Result<Verdict, AocError> {
+    let url = format!("{}/answer", client.day_url(day));
+    let level = part.to_string();
+    let answer = answer.to_string();
+
+    let html = client.post_form(&url, &[("level", &level), ("answer", &answer)])?;
+    Ok(Verdict::from_response(&html))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    AlreadyCorrect(String),
+    KnownWrong(Verdict),
+    NotBelow(String),
+    NotAbove(String),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect(answer) => write!(f, "already solved with {}", answer),
+            Refusal::KnownWrong(verdict) => write!(f, "already submitted: {}", verdict),
+            Refusal::NotBelow(bound) => {
+                write!(f, "must be below {}, which was too high", bound)
+            }
+            Refusal::NotAbove(bound) => write!(f, "must be above {}, which was too low", bound),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct AttemptLog {
+    pub attempts: Vec<Attempt>,
+}
+
+impl AttemptLog {
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| AocError::Attempts(e.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(AocError::from(e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AocError> {
+        let text =
+            serde_json::to_string_pretty(self).map_err(|e| AocError::Attempts(e.to_string()))?;
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    fn for_part(&self, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    // Checks a candidate against earlier attempts: exact repeats of wrong
+    // answers are refused, and numeric answers must lie strictly between the
+    // highest "too low" and the lowest "too high" seen so far.
+    pub fn check(&self, day: u8, part: u8, answer: &Answer) -> Option<Refusal> {
+        if let Some(correct) = self
+            .for_part(day, part)
+            .find(|a| a.verdict == Verdict::Correct)
+        {
+            return Some(Refusal::AlreadyCorrect(correct.answer.clone()));
+        }
+
+        if let Some(wrong) = self
+            .for_part(day, part)
+            .find(|a| a.verdict.is_wrong() && answer.matches(&a.answer))
+        {
+            return Some(Refusal::KnownWrong(wrong.verdict.clone()));
+        }
+
+        let value = answer.to_string().parse::<i128>().ok()?;
+        let bound = |verdict: Verdict| {
+            self.for_part(day, part)
+                .filter(move |a| a.verdict == verdict)
+                .filter_map(|a| a.answer.parse::<i128>().ok())
+        };
+
+        if let Some(high) = bound(Verdict::TooHigh).min().filter(|high| value >= *high) {
+            return Some(Refusal::NotBelow(high.to_string()));
+        }
+        if let Some(low) = bound(Verdict::TooLow).max().filter(|low| value <= *low) {
+            return Some(Refusal::NotAbove(low.to_string()));
+        }
+
+        None
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: &Answer, verdict: Verdict) {
+        self.attempts.push(Attempt {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp: unix_timestamp(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod submit_tests {
+    use super::*;
+    use crate::test_server::TestServer;
+
+    fn article(text: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            text
+        )
+    }
+
+    macro_rules! verdict_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (text, expected) = $value;
+                    assert_eq!(expected, Verdict::from_response(&article(text)))
+                }
+            )*
+        }
+    }
+
+    verdict_tests! {
+        right_answer_is_correct: (
+            "That's the right answer! You are <em>one gold star</em> closer.",
+            Verdict::Correct,
+        ),
+        too_high_is_parsed: (
+            "That's not the right answer; your answer is too high.",
+            Verdict::TooHigh,
+        ),
+        too_low_is_parsed: (
+            "That's not the right answer; your answer is too low.",
+            Verdict::TooLow,
+        ),
+        plain_wrong_is_parsed: (
+            "That's not the right answer. If you're stuck, make sure you're using the full input.",
+            Verdict::Wrong,
+        ),
+        rate_limit_keeps_wait: (
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again. You have 4m 12s left to wait.",
+            Verdict::RateLimited { wait: Some(String::from("4m 12s")) },
+        ),
+        wrong_level_is_already_solved: (
+            "You don't seem to be solving the right level. Did you already complete it?",
+            Verdict::AlreadySolved,
+        ),
+        other_text_is_unknown: (
+            "Please <a href=\"/auth\">log in</a>.",
+            Verdict::Unknown(String::from("Please log in.")),
+        ),
+    }
+
+    fn log(attempts: &[(&str, Verdict)]) -> AttemptLog {
+        let mut log = AttemptLog::default();
+        for (answer, verdict) in attempts {
+            log.record(1, 1, &Answer::from(*answer), verdict.clone());
+        }
+        log
+    }
+
+    #[test]
+    fn check_refuses_known_wrong_answer() {
+        let log = log(&[("42", Verdict::Wrong)]);
+
+        assert_eq!(
+            Some(Refusal::KnownWrong(Verdict::Wrong)),
+            log.check(1, 1, &Answer::from(42))
+        )
+    }
+
+    #[test]
+    fn check_refuses_values_outside_learned_bounds() {
+        let log = log(&[
+            ("100", Verdict::TooHigh),
+            ("80", Verdict::TooHigh),
+            ("10", Verdict::TooLow),
+        ]);
+
+        assert_eq!(
+            Some(Refusal::NotBelow(String::from("80"))),
+            log.check(1, 1, &Answer::from(90))
+        );
+        assert_eq!(
+            Some(Refusal::NotAbove(String::from("10"))),
+            log.check(1, 1, &Answer::from(-5))
+        );
+        assert_eq!(None, log.check(1, 1, &Answer::from(79)));
+        assert_eq!(None, log.check(1, 2, &Answer::from(90)))
+    }
+
+    #[test]
+    fn check_refuses_part_already_solved() {
+        let log = log(&[("12", Verdict::TooLow), ("15", Verdict::Correct)]);
+
+        assert_eq!(
+            Some(Refusal::AlreadyCorrect(String::from("15"))),
+            log.check(1, 1, &Answer::from(20))
+        )
+    }
+
+    #[test]
+    fn check_ignores_rate_limited_attempts() {
+        let log = log(&[("42", Verdict::RateLimited { wait: None })]);
+
+        assert_eq!(None, log.check(1, 1, &Answer::from(42)))
+    }
+
+    #[test]
+    fn submit_posts_level_and_answer() {
+        let server = TestServer::start(vec![(
+            "/2023/day/4/answer",
+            200,
+            article("That's not the right answer; your answer is too low."),
+        )]);
+        let client = Client::new("abc123").with_base_url(&server.base_url);
+
+        let verdict = submit(&client, 4, 2, &Answer::from(1234)).unwrap();
+
+        assert_eq!(Verdict::TooLow, verdict);
+        let requests = server.requests();
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("level=2&answer=1234", requests[0].body);
+        assert_eq!(Some(String::from("session=abc123")), requests[0].cookie)
+    }
+}
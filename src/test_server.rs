@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+// A tiny HTTP/1.1 stand-in for the puzzle site: serves canned responses by
+// path and records every request it sees. One connection per request.
+pub struct TestServer {
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl TestServer {
+    pub fn start(routes: Vec<(&str, u16, String)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let routes: HashMap<String, (u16, String)> = routes
+            .into_iter()
+            .map(|(path, status, body)| (path.to_string(), (status, body)))
+            .collect();
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let seen = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handle(stream, &routes, &seen);
+            }
+        });
+
+        Self { base_url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn handle(
+    stream: TcpStream,
+    routes: &HashMap<String, (u16, String)>,
+    seen: &Mutex<Vec<Request>>,
+) -> Option<()> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut cookie = None;
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).ok()?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header.split_once(':')?;
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => cookie = Some(value.trim().to_string()),
+            "content-length" => length = value.trim().parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; length];
+    std::io::Read::read_exact(&mut reader, &mut body).ok()?;
+
+    // Recorded before responding so a client never sees its own request
+    // missing once the response has arrived.
+    seen.lock().unwrap().push(Request {
+        method,
+        path: path.clone(),
+        cookie,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    });
+
+    let (status, response) = routes
+        .get(&path)
+        .cloned()
+        .unwrap_or((404, String::from("not found")));
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} TEST\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        response.len(),
+        response
+    )
+    .ok()
+}
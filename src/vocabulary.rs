@@ -0,0 +1,443 @@
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::automaton::{Automaton, Match};
+use crate::digit_scan::{first_calibration_digit, last_calibration_digit};
+use crate::error::AocError;
+use crate::string_utils::{digit_run_zeros, DigitSet};
+
+const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const GERMAN: [(&str, u32); 9] = [
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+const FRENCH: [(&str, u32); 9] = [
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+const SPANISH: [(&str, u32); 9] = [
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DigitMatch {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+impl DigitMatch {
+    pub fn text<'a>(&self, line: &'a str) -> &'a str {
+        &line[self.start..self.end]
+    }
+}
+
+struct DigitPatterns {
+    automaton: Automaton,
+    values: Vec<u32>,
+}
+
+// The number words and numerals that count as digits when reading a
+// calibration line. The matching automaton is built on first use.
+pub struct DigitVocabulary {
+    words: Vec<(String, u32)>,
+    digits: DigitSet,
+    case_insensitive: bool,
+    patterns: OnceLock<DigitPatterns>,
+}
+
+impl fmt::Debug for DigitVocabulary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DigitVocabulary")
+            .field("words", &self.words)
+            .field("digits", &self.digits)
+            .field("case_insensitive", &self.case_insensitive)
+            .finish()
+    }
+}
+
+// Each of these letters doubles the case variants spelled out for a word,
+// so a word may have only a few.
+const MAX_CASED_LETTERS: usize = 10;
+
+fn single(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
+// `c` and its other case, if it needs spelling out. ASCII letters are left
+// alone because the automaton folds them itself, and letters whose other
+// case is more than one char (like 'ß') are kept as is.
+fn case_options(c: char) -> Vec<char> {
+    let mut options = vec![c];
+    if !c.is_ascii() {
+        let others = [single(c.to_lowercase()), single(c.to_uppercase())];
+        for other in others.into_iter().flatten() {
+            if !options.contains(&other) {
+                options.push(other);
+            }
+        }
+    }
+    options
+}
+
+fn cased_letters(word: &str) -> usize {
+    word.chars().filter(|c| case_options(*c).len() > 1).count()
+}
+
+// Every spelling of `word` with its non-ASCII letters in either case.
+fn case_variants(word: &str) -> Vec<String> {
+    let mut variants = vec![String::new()];
+
+    for c in word.chars() {
+        let options = case_options(c);
+        variants = variants
+            .iter()
+            .flat_map(|prefix| {
+                options.iter().map(move |c| {
+                    let mut variant = prefix.clone();
+                    variant.push(*c);
+                    variant
+                })
+            })
+            .collect();
+    }
+
+    variants
+}
+
+impl DigitVocabulary {
+    pub fn digits_only() -> Self {
+        Self {
+            words: vec![],
+            digits: DigitSet::Ascii,
+            case_insensitive: false,
+            patterns: OnceLock::new(),
+        }
+    }
+
+    pub fn from_table<S: AsRef<str>>(table: &[(S, u32)]) -> Result<Self, AocError> {
+        let words = table
+            .iter()
+            .map(|(word, value)| {
+                let word = word.as_ref();
+                if word.is_empty() {
+                    return Err(AocError::Vocabulary(String::from(
+                        "number words must not be empty",
+                    )));
+                }
+                if cased_letters(word) > MAX_CASED_LETTERS {
+                    return Err(AocError::Vocabulary(format!(
+                        "'{}' has more than {} non-ASCII letters with another case",
+                        word, MAX_CASED_LETTERS
+                    )));
+                }
+                if *value > 9 {
+                    return Err(AocError::Vocabulary(format!(
+                        "'{}' must stand for a single digit, not {}",
+                        word, value
+                    )));
+                }
+                Ok((word.to_string(), *value))
+            })
+            .collect::<Result<Vec<(String, u32)>, AocError>>()?;
+
+        Ok(Self {
+            words,
+            ..Self::digits_only()
+        })
+    }
+
+    fn built_in<S: AsRef<str>>(table: &[(S, u32)]) -> Self {
+        Self::from_table(table).expect("built-in number word tables are valid")
+    }
+
+    pub fn english() -> Self {
+        Self::built_in(&ENGLISH)
+    }
+
+    pub fn german() -> Self {
+        Self::built_in(&GERMAN)
+    }
+
+    pub fn french() -> Self {
+        Self::built_in(&FRENCH)
+    }
+
+    pub fn spanish() -> Self {
+        Self::built_in(&SPANISH)
+    }
+
+    pub fn case_insensitive(self) -> Self {
+        Self {
+            case_insensitive: true,
+            patterns: OnceLock::new(),
+            ..self
+        }
+    }
+
+    pub fn with_digits(self, digits: DigitSet) -> Self {
+        Self {
+            digits,
+            patterns: OnceLock::new(),
+            ..self
+        }
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words
+            .iter()
+            .map(|(word, value)| (word.as_str(), *value))
+    }
+
+    pub fn digits(&self) -> DigitSet {
+        self.digits
+    }
+
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    // Every literal this vocabulary matches, numerals first, with the digit
+    // it stands for. When case insensitive, ASCII letters still have to be
+    // compared without case; only non-ASCII variants are spelled out.
+    pub fn pattern_table(&self) -> Vec<(String, u32)> {
+        let mut table = vec![];
+
+        for zero in digit_run_zeros(self.digits) {
+            for value in 1..=9 {
+                let c = char::from_u32(zero + value).expect("digit runs are valid chars");
+                table.push((c.to_string(), value));
+            }
+        }
+
+        for (word, value) in self.words() {
+            if self.case_insensitive {
+                table.extend(case_variants(word).into_iter().map(|v| (v, value)));
+            } else {
+                table.push((word.to_string(), value));
+            }
+        }
+
+        table
+    }
+
+    fn build(&self) -> DigitPatterns {
+        let (patterns, values): (Vec<String>, Vec<u32>) = self.pattern_table().into_iter().unzip();
+
+        let automaton = match self.case_insensitive {
+            true => Automaton::ascii_case_insensitive(patterns),
+            false => Automaton::new(patterns),
+        };
+
+        DigitPatterns { automaton, values }
+    }
+
+    fn patterns(&self) -> &DigitPatterns {
+        self.patterns.get_or_init(|| self.build())
+    }
+
+    fn to_digit_match(&self, found: Match) -> DigitMatch {
+        DigitMatch {
+            start: found.start,
+            end: found.end,
+            value: self.patterns().values[found.pattern],
+        }
+    }
+
+    // Plain ASCII digits need no automaton; a byte scan finds them faster.
+    fn is_ascii_digits_only(&self) -> bool {
+        self.words.is_empty() && self.digits == DigitSet::Ascii
+    }
+
+    fn ascii_digit_match(input: &str, start: usize) -> DigitMatch {
+        DigitMatch {
+            start,
+            end: start + 1,
+            value: (input.as_bytes()[start] - b'0') as u32,
+        }
+    }
+
+    pub fn first_match(&self, input: &str) -> Option<DigitMatch> {
+        if self.is_ascii_digits_only() {
+            let start = first_calibration_digit(input.as_bytes())?;
+            return Some(Self::ascii_digit_match(input, start));
+        }
+
+        let found = self.patterns().automaton.find_first(input.as_bytes())?;
+        Some(self.to_digit_match(found))
+    }
+
+    pub fn last_match(&self, input: &str) -> Option<DigitMatch> {
+        if self.is_ascii_digits_only() {
+            let start = last_calibration_digit(input.as_bytes())?;
+            return Some(Self::ascii_digit_match(input, start));
+        }
+
+        let found = self.patterns().automaton.find_last(input.as_bytes())?;
+        Some(self.to_digit_match(found))
+    }
+}
+
+#[cfg(test)]
+mod vocabulary_tests {
+    use super::*;
+
+    fn first_and_last(vocabulary: &DigitVocabulary, input: &str) -> Option<(u32, u32)> {
+        let first = vocabulary.first_match(input)?;
+        let last = vocabulary.last_match(input)?;
+        Some((first.value, last.value))
+    }
+
+    macro_rules! vocabulary_tests {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (vocabulary, input, expected) = $value;
+                    assert_eq!(expected, first_and_last(&vocabulary, input))
+                }
+            )*
+        }
+    }
+
+    vocabulary_tests! {
+        english_overlapping_words: (DigitVocabulary::english(), "xtwone3four", Some((2, 4))),
+        german_words: (DigitVocabulary::german(), "abzweiXdreiundvierzig", Some((2, 4))),
+        german_umlaut: (DigitVocabulary::german(), "fünfzehn", Some((5, 5))),
+        french_words: (DigitVocabulary::french(), "septante-huit", Some((7, 8))),
+        french_un_inside_words: (DigitVocabulary::french(), "lundi 6", Some((1, 6))),
+        spanish_words: (DigitVocabulary::spanish(), "nueve y ocho", Some((9, 8))),
+        spanish_overlap: (DigitVocabulary::spanish(), "dosiete", Some((2, 7))),
+        digits_only_ignores_words: (DigitVocabulary::digits_only(), "one2three", Some((2, 2))),
+        words_are_case_sensitive_by_default: (DigitVocabulary::english(), "ONE2Three", Some((2, 2))),
+        case_insensitive_ascii: (
+            DigitVocabulary::english().case_insensitive(),
+            "ONE2Three",
+            Some((1, 3)),
+        ),
+        case_insensitive_umlaut: (
+            DigitVocabulary::german().case_insensitive(),
+            "FÜNF und ZWEI",
+            Some((5, 2)),
+        ),
+        unicode_numerals_with_words: (
+            DigitVocabulary::french().with_digits(DigitSet::Unicode),
+            "\u{ff13}deux",
+            Some((3, 2)),
+        ),
+        no_digits_is_none: (DigitVocabulary::english(), "nothing", None),
+    }
+
+    #[test]
+    fn matches_carry_byte_spans() {
+        let vocabulary = DigitVocabulary::german();
+        let line = "fünfzehn";
+
+        let first = vocabulary.first_match(line).unwrap();
+
+        assert_eq!((0, 5, 5), (first.start, first.end, first.value));
+        assert_eq!("fünf", first.text(line))
+    }
+
+    #[test]
+    fn from_table_allows_custom_words_and_zero() {
+        let vocabulary = DigitVocabulary::from_table(&[("zero", 0), ("zwei", 2)]).unwrap();
+
+        assert_eq!(Some((0, 2)), first_and_last(&vocabulary, "zero oder zwei"));
+        assert_eq!(2, vocabulary.words().count())
+    }
+
+    #[test]
+    fn from_table_rejects_multi_digit_values() {
+        match DigitVocabulary::from_table(&[("ten", 10)]) {
+            Err(AocError::Vocabulary(message)) => {
+                assert_eq!("'ten' must stand for a single digit, not 10", message)
+            }
+            other => panic!("expected vocabulary error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn from_table_rejects_empty_words() {
+        assert!(matches!(
+            DigitVocabulary::from_table(&[("", 1)]),
+            Err(AocError::Vocabulary(_))
+        ))
+    }
+
+    #[test]
+    fn from_table_rejects_words_with_too_many_cased_letters() {
+        let word = "ü".repeat(MAX_CASED_LETTERS + 1);
+
+        match DigitVocabulary::from_table(&[(word.as_str(), 1)]) {
+            Err(AocError::Vocabulary(message)) => assert_eq!(
+                format!(
+                    "'{}' has more than 10 non-ASCII letters with another case",
+                    word
+                ),
+                message
+            ),
+            other => panic!("expected vocabulary error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn from_table_accepts_words_at_the_cased_letter_limit() {
+        let word = "ü".repeat(MAX_CASED_LETTERS);
+        let vocabulary = DigitVocabulary::from_table(&[(word.as_str(), 4)])
+            .unwrap()
+            .case_insensitive();
+
+        // The nine numerals, then every spelling of the word.
+        assert_eq!(
+            9 + (1 << MAX_CASED_LETTERS),
+            vocabulary.pattern_table().len()
+        );
+        assert_eq!(
+            Some((4, 4)),
+            first_and_last(&vocabulary, &"Ü".repeat(MAX_CASED_LETTERS))
+        )
+    }
+
+    #[test]
+    fn case_variants_only_expand_non_ascii_letters() {
+        assert_eq!(vec!["fünf", "fÜnf"], case_variants("fünf"));
+        assert_eq!(vec!["Eins"], case_variants("Eins"))
+    }
+}